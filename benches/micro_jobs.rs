@@ -1,4 +1,5 @@
 use criterion::{criterion_group, criterion_main, Criterion, BatchSize};
+use std::hint::black_box;
 use std::time::{Duration, Instant};
 use rayon::prelude::*;
 use parallel_task::prelude::*;
@@ -106,5 +107,36 @@ fn bench_micro_jobs(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, bench_micro_jobs);
+// Jobs of a few nanoseconds each, where the per-value overhead of scheduling them across the worker threads makes up
+// most of the time
+fn tiny_job(val: u64) -> u64 {
+    let mut acc = val;
+    for _ in 0..8 {
+        acc = acc.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+    }
+    acc
+}
+
+fn bench_tiny_jobs(c: &mut Criterion) {
+    let values = (0..100_000u64).collect::<Vec<u64>>();
+
+    c.bench_function("Normal (tiny)", |b| {
+        b.iter(|| values.iter().map(|val| tiny_job(black_box(*val))).collect::<Vec<u64>>());
+    });
+
+    c.bench_function("Rayon (tiny)", |b| {
+        b.iter(|| values.par_iter().map(|val| tiny_job(black_box(*val))).collect::<Vec<u64>>());
+    });
+
+    c.bench_function("PT (tiny)", |b| {
+        b.iter(|| values.parallel_iter().map(|val| tiny_job(black_box(*val))).collect::<Vec<u64>>());
+    });
+
+    // A chain of adapters calls into the closures several times per value
+    c.bench_function("PT (tiny chain)", |b| {
+        b.iter(|| values.parallel_iter().map(|val| *val).filter(|val| val % 3 != 0).map(tiny_job).sum::<u64>());
+    });
+}
+
+criterion_group!(benches, bench_micro_jobs, bench_tiny_jobs);
 criterion_main!(benches);
@@ -1,67 +1,190 @@
 //! LimitAccessQueue is where the queue is stored and managed. This may only be accessed via the accessors.
-use crate::utils::SpinWait;
+//!
+//! The queue is a Chase-Lev work-stealing deque. The secondary accessor, held by the ThreadRunner, pops values from the
+//! bottom, which needs no compare-exchange unless it races a stealer for the last value. The primary accessor, held by
+//! the controller, steals values from the top, one compare-exchange per value. Values are written at the bottom by
+//! the primary. Popping and writing both move the bottom, so they are kept apart by the state: the primary only writes
+//! while the runner is Waiting, and the runner only pops once it has been set to Run. The state is kept in an atomic
+//! and is never behind a lock. The top, the bottom and the state each sit on their own cache line, so that stealing
+//! from the top does not contend with popping from the bottom.
 use super::read_accessor::*;
-use std::sync::{atomic::AtomicBool, Arc};
+use std::cell::UnsafeCell;
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::ops::Deref;
+use std::sync::{atomic::{fence, AtomicIsize, AtomicPtr, AtomicU8, Ordering}, Arc};
+
+const MIN_CAPACITY:usize = 64;
+
+/// State shared by the accessors of a queue. It is kept as a u8, so that it may be read or set atomically.
+pub trait QueueState: Clone + Default + Into<u8> + From<u8> {}
+
+impl<S> QueueState for S
+where S: Clone + Default + Into<u8> + From<u8> {}
+
+/// Circular buffer holding the values of the queue. Its capacity is a power of two, so that the positions of the
+/// queue, which only ever grow, wrap around with a mask.
+struct Buffer<T> {
+    ptr: *mut MaybeUninit<T>,
+    cap: usize
+}
+
+impl<T> Buffer<T> {
+    fn alloc(cap:usize) -> Self {
+        let mut slots:Vec<MaybeUninit<T>> = Vec::with_capacity(cap);
+        let ptr = slots.as_mut_ptr();
+        std::mem::forget(slots);
+        Self { ptr, cap }
+    }
+
+    unsafe fn slot(&self, index:isize) -> *mut MaybeUninit<T> {
+        self.ptr.add(index as usize & (self.cap - 1))
+    }
+
+    unsafe fn write(&self, index:isize, value:T) {
+        self.slot(index).write(MaybeUninit::new(value));
+    }
+
+    /// Copies the value out of the slot. The copy is only owned once the position has been claimed.
+    unsafe fn read(&self, index:isize) -> MaybeUninit<T> {
+        self.slot(index).read()
+    }
+}
+
+impl<T> Drop for Buffer<T> {
+    fn drop(&mut self) {
+        // The values have been moved out or dropped by the queue, so only the allocation is freed here
+        unsafe { drop(Vec::from_raw_parts(self.ptr, 0, self.cap)); }
+    }
+}
+
+/// Keeps a value on a cache line of its own
+#[repr(align(64))]
+struct CachePadded<T>(T);
+
+impl<T> Deref for CachePadded<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
 
 pub struct LimitAccessQueue<T,State> {
-    pub val: Vec<T>,    
-    write_block: AtomicBool,
-    state: State
+    top: CachePadded<AtomicIsize>,
+    bottom: CachePadded<AtomicIsize>,
+    state: CachePadded<AtomicU8>,
+    buffer: AtomicPtr<Buffer<T>>,
+    // Buffers outgrown by the queue. They are kept till the queue is dropped, as a stealer may still be reading them.
+    retired: UnsafeCell<Vec<Buffer<T>>>,
+    s: PhantomData<fn() -> State>
 }
 
+// SAFETY: the values are handed between threads by value, and the positions of the queue are claimed through the
+// atomics top and bottom so that each value is taken once. The bottom, the buffer and retired are only changed by
+// pop and write, which the state keeps from running at the same time.
+unsafe impl<T: Send,State> Send for LimitAccessQueue<T,State> {}
+unsafe impl<T: Send,State> Sync for LimitAccessQueue<T,State> {}
+
 #[allow(dead_code,clippy::new_ret_no_self)]
-impl<T,State> LimitAccessQueue<T,State> 
-where State: Default + Clone
+impl<T,State> LimitAccessQueue<T,State>
+where State: QueueState
 {
     pub fn new() -> (PrimaryAccessor<T,State>,SecondaryAccessor<T,State>) {
         let arc_obj = Arc::new(Self {
-            val: Vec::new(),            
-            write_block: AtomicBool::new(false),            
-            state: State::default()            
-        });        
-        
+            top: CachePadded(AtomicIsize::new(0)),
+            bottom: CachePadded(AtomicIsize::new(0)),
+            state: CachePadded(AtomicU8::new(State::default().into())),
+            buffer: AtomicPtr::new(Box::into_raw(Box::new(Buffer::alloc(MIN_CAPACITY)))),
+            retired: UnsafeCell::new(Vec::new()),
+            s: PhantomData
+        });
+
         //we need to ensure the object within AtomicPtr survives on the heap and beyond
-        //the function stack.                   
+        //the function stack.
         let primary = ReadAccessor::new(arc_obj.clone(),ReadAccessorType::Primary);
-        let secondary = ReadAccessor::new(arc_obj,ReadAccessorType::Secondary);             
+        let secondary = ReadAccessor::new(arc_obj,ReadAccessorType::Secondary);
         (PrimaryAccessor::new(primary), SecondaryAccessor::new(secondary))
-                     
-       
     }
 
-    pub fn set_state(&mut self, state:State) {
-        self.with_write_block(|s|{
-            s.state = state;
-        });
+    pub fn set_state(&self, state:State) {
+        self.state.store(state.into(), Ordering::SeqCst);
     }
 
-    pub fn get_state(&mut self) -> State {
-        self.with_write_block(|s|{
-            s.state.clone()
-        })
+    pub fn get_state(&self) -> State {
+        State::from(self.state.load(Ordering::SeqCst))
     }
 
-    pub fn pop(&mut self) -> Option<T> {
-        self.with_write_block(|s| { 
-            s.val.pop()
-        })       
+    /// Sets the state to new only if it currently is the expected state. Returns the state found.
+    pub fn compare_exchange_state(&self, current:State, new:State) -> Result<State,State> {
+        self.state.compare_exchange(current.into(), new.into(), Ordering::SeqCst, Ordering::SeqCst)
+        .map(State::from)
+        .map_err(State::from)
     }
 
-    pub fn pop_count(&mut self,count:usize) -> Option<Vec<T>> {
-        self.with_write_block(|s| { 
-            let mut res = Vec::new();
-            for idx in 0..count {
-                if let Some(val) = s.val.pop() {
-                    res.push(val)
-                } else {
-                    if idx == 0 {
-                        return None;
-                    }
-                    break;
-                }
-            }   
-            Some(res)         
-        })       
+    /// Pops the value at the bottom. Only the runner pops, and never while values are written, so this claims the
+    /// value without a compare-exchange unless it is the last one, which a stealer may be taking at the same time.
+    pub fn pop(&self) -> Option<T> {
+        let b = self.bottom.load(Ordering::Relaxed) - 1;
+        let buffer = self.buffer.load(Ordering::Relaxed);
+        self.bottom.store(b, Ordering::Relaxed);
+        fence(Ordering::SeqCst);
+        let t = self.top.load(Ordering::Relaxed);
+
+        if t > b {
+            self.bottom.store(b + 1, Ordering::Relaxed);
+            return None;
+        }
+        // SAFETY: positions from top up to bottom hold values, and the buffer is not replaced while popping
+        let value = unsafe { (*buffer).read(b) };
+        if t == b {
+            let won = self.top.compare_exchange(t, t + 1, Ordering::SeqCst, Ordering::Relaxed).is_ok();
+            self.bottom.store(b + 1, Ordering::Relaxed);
+            if !won {
+                // The stealer owns the value, so the copy is left without being dropped
+                return None;
+            }
+        }
+        // SAFETY: the position has been claimed, so the copy is the only owner of the value
+        Some(unsafe { value.assume_init() })
+    }
+
+    pub fn pop_count(&self,count:usize) -> Option<Vec<T>> {
+        let res = std::iter::from_fn(|| self.pop()).take(count).collect::<Vec<T>>();
+        if res.is_empty() { None } else { Some(res) }
+    }
+
+    /// Steals the value at the top. A stealer that loses the race for the value, to the runner or to another
+    /// stealer, tries again with the next one.
+    pub fn steal_one(&self) -> Option<T> {
+        loop {
+            let t = self.top.load(Ordering::Acquire);
+            fence(Ordering::SeqCst);
+            let b = self.bottom.load(Ordering::Acquire);
+            if t >= b {
+                return None;
+            }
+            let buffer = self.buffer.load(Ordering::Acquire);
+            // SAFETY: the buffer holds the position, as any buffer replacing it was filled before bottom was moved.
+            // Retired buffers are kept alive, so the read is valid even if the buffer has been outgrown meanwhile.
+            let value = unsafe { (*buffer).read(t) };
+            if self.top.compare_exchange(t, t + 1, Ordering::SeqCst, Ordering::Relaxed).is_ok() {
+                // SAFETY: the position has been claimed, so the copy is the only owner of the value
+                return Some(unsafe { value.assume_init() });
+            }
+        }
+    }
+
+    /// Steals up to count values from the top, in the order they were written
+    pub fn steal_count(&self, count:usize) -> Option<Vec<T>> {
+        let mut res = Vec::with_capacity(usize::min(count, self.len()));
+        while res.len() < count {
+            match self.steal_one() {
+                Some(value) => res.push(value),
+                None => break
+            }
+        }
+        if res.is_empty() { None } else { Some(res) }
     }
 
     ///Steals all the un-popped values from the queue. It can then be reused
@@ -76,17 +199,8 @@ where State: Default + Clone
     /// let vec = primary.steal().unwrap(); //This step should not fail here. But unwrap not advised in production
     /// assert_eq!(vec.len(), 100_000);
     /// ```
-    pub fn steal(&mut self) -> Option<Vec<T>> {         
-        self.with_write_block(|s| {
-            if s.val.is_empty() {
-                None
-            } else {
-                // using mem swap to expedite the process
-                let mut tmp:Vec<T> = Vec::with_capacity(1);
-                std::mem::swap(&mut tmp, &mut s.val);
-                Some(tmp)            
-            }        
-        })              
+    pub fn steal(&self) -> Option<Vec<T>> {
+        self.steal_count(usize::MAX)
     }
 
     ///Steals half the un-popped values from the queue. It can then be reused
@@ -101,71 +215,82 @@ where State: Default + Clone
     /// let vec = primary.steal_half().unwrap(); //This step should not fail here. But unwrap not advised in production
     /// assert_eq!(vec.len(), 50_000);
     /// ```
-    pub fn steal_half(&mut self) -> Option<Vec<T>> {          
-        self.with_write_block(|s| {         
-            if s.val.is_empty() {                
-                None
-            } 
-            else {
-                let res = s.val.split_off(s.val.len()/2);          
-                Some(res)            
-            }            
-        })                      
+    pub fn steal_half(&self) -> Option<Vec<T>> {
+        let len = self.len();
+        if len == 0 {
+            None
+        } else {
+            self.steal_count(len - len / 2)
+        }
     }
 
-    pub fn is_empty(&mut self) -> bool { 
-        self.len() == 0              
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 
-    pub fn len(&mut self) -> usize {         
-        self.with_write_block(|s|{
-            if s.val.is_empty() { 0usize } else { s.val.len() }
-        })        
+    pub fn len(&self) -> usize {
+        let b = self.bottom.load(Ordering::Relaxed);
+        let t = self.top.load(Ordering::Relaxed);
+        usize::try_from(b - t).unwrap_or_default()
     }
 
-    pub fn atomic_write_block_to_true(&mut self) -> Result<bool, bool> {
-        self.write_block.compare_exchange(false, true, std::sync::atomic::Ordering::SeqCst, std::sync::atomic::Ordering::SeqCst)
-    }    
-
-    pub fn with_write_block<F,Output>(&mut self, f:F) -> Output
-    where F: FnOnce(&mut Self) -> Output {                          
-        SpinWait::loop_while_mut(||self.atomic_write_block_to_true().is_err());                            
-        let output = f(self);
-        self.write_block.store(false, std::sync::atomic::Ordering::SeqCst);                 
-        output
+    pub(crate) fn push(&self, value:T) {
+        self.write(vec![value]);
     }
 
-    pub fn push(&mut self, value:T) {
-
-        self.with_write_block(|s|
-        {
-            s.val.push(value);
-        });        
+    /// Writes the values at the bottom, so that the last of them is popped first. Must not run while the runner pops,
+    /// which the accessors ensure by only writing while the runner is Waiting.
+    pub(crate) fn write(&self, values:Vec<T>) {
+        if values.is_empty() {
+            return;
+        }
+        let count = values.len();
+        let b = self.bottom.load(Ordering::Relaxed);
+        let t = self.top.load(Ordering::Acquire);
+        let mut buffer = self.buffer.load(Ordering::Relaxed);
+        let needed = usize::try_from(b - t).unwrap_or_default() + count;
+        // SAFETY: the buffer is only replaced by write
+        if needed > unsafe { (*buffer).cap } {
+            buffer = self.grow(t, b, needed);
+        }
+        for (offset, value) in values.into_iter().enumerate() {
+            // SAFETY: the positions from bottom on are not read till bottom is moved past them
+            unsafe { (*buffer).write(b + offset as isize, value); }
+        }
+        fence(Ordering::Release);
+        self.bottom.store(b + count as isize, Ordering::Relaxed);
     }
 
-    pub fn write(&mut self, mut values:Vec<T>) {     
-        self.with_write_block(|s|{
-            let drained = values.drain(0..);                
-            s.val.extend(drained); 
-        });                                         
+    /// Replaces the values in the queue, dropping those not yet popped
+    pub(crate) fn replace(&self, values:Vec<T>) {
+        drop(self.steal());
+        self.write(values);
     }
 
-    pub fn replace(&mut self, mut values:Vec<T>) {    
-        self.with_write_block(|s|{             
-            std::mem::swap(&mut values, &mut s.val);                      
-        });                            
+    /// Moves the values from top to bottom into a buffer with room for needed values. Only called by write.
+    fn grow(&self, t:isize, b:isize, needed:usize) -> *mut Buffer<T> {
+        let old = self.buffer.load(Ordering::Relaxed);
+        let new = Buffer::alloc(needed.next_power_of_two().max(MIN_CAPACITY));
+        for index in t..b {
+            // SAFETY: the values are moved bitwise, and the old buffer is kept without dropping them
+            unsafe { new.slot(index).write((*old).read(index)); }
+        }
+        let new = Box::into_raw(Box::new(new));
+        self.buffer.store(new, Ordering::Release);
+        // SAFETY: retired is only touched by write, and old was made by Box::into_raw
+        unsafe { (*self.retired.get()).push(*Box::from_raw(old)); }
+        new
     }
+}
 
-    pub fn is_write_blocked(&self) -> bool {
-        self.write_block.load(std::sync::atomic::Ordering::SeqCst)
+impl<T,State> Drop for LimitAccessQueue<T,State> {
+    fn drop(&mut self) {
+        let t = self.top.load(Ordering::Relaxed);
+        let b = self.bottom.load(Ordering::Relaxed);
+        // SAFETY: the queue is no longer shared, and the current buffer was made by Box::into_raw
+        let buffer = unsafe { Box::from_raw(*self.buffer.get_mut()) };
+        for index in t..b {
+            unsafe { drop(buffer.read(index).assume_init()); }
+        }
     }
-
-    // pub fn ingest_iter<I>(&mut self, mut i:I)
-    // where I:AccessQueueIngestor<IngestorItem = T>
-    // {
-    //     while let Some(value) = i.next_chunk() {
-    //         self.push(value);
-    //     }
-    // }
-
-}
\ No newline at end of file
+}
@@ -2,13 +2,14 @@
 //! to the queue itself. But it gives access to the primary and secondary accessors. These may then be moved in to separate
 //! threads to then manage the access to the queue. Primary has the additional ability to steal. Secondary cannot steal.
 //! If primary pushes a new set of tasks and changes the status, the secondary can pull the same on command.
+//! The secondary pops from one end of the queue while the primary pops and steals from the other.
 //! The reason for just two accessors is to create a synchronised atomics based management of queue across threads. The accessors
 //! are inherently fast compared to channels and do not engage Locks.
 //! The accessors cannot be cloned.
 
 use std::{ops::{Deref, DerefMut}, sync::{atomic::AtomicPtr, Arc}};
 
-use crate::{accessors::limit_queue::LimitAccessQueue};
+use crate::accessors::limit_queue::{LimitAccessQueue, QueueState};
 
 
 /// Adds a primary and secondary accessor to easily differentiate the read accessors during usage
@@ -101,7 +102,7 @@ pub struct ReadAccessor<T,State>
 
 #[allow(dead_code)]
 impl<T,State> ReadAccessor<T,State> 
-where State: Clone + Default
+where State: QueueState
 {
     pub fn new(owner:Arc<LimitAccessQueue<T,State>>, rtype:ReadAccessorType) -> Self {
         let arc_ptr = Arc::as_ptr(&owner) as *mut LimitAccessQueue<T,State>;
@@ -135,21 +136,11 @@ where State: Clone + Default
         }       
     }
 
-    fn get_mut(&self) -> Option<&mut LimitAccessQueue<T,State>> {
-        unsafe {  
-            if let Some(ptr_ref) = self.as_ptr() {
-                (ptr_ref).as_mut()                    
-            }  else {
-                None
-            }                    
-        }       
-    }
-
-    fn within_mutable_block<F,Output>(&self,f:F) -> Option<Output>
-    where F: FnOnce(&mut LimitAccessQueue<T,State>) -> Option<Output> {
+    fn within_block<F,Output>(&self,f:F) -> Option<Output>
+    where F: FnOnce(&LimitAccessQueue<T,State>) -> Option<Output> {
 
-        if let Some(ptr) = self.get_mut() {
-            f(ptr)                       
+        if let Some(queue) = self.get_ref() {
+            f(queue)                       
         } else {
             None
         }        
@@ -159,43 +150,57 @@ where State: Clone + Default
         self.rtype == ReadAccessorType::Primary
     }
 
+    /// The secondary pops the value written last. The primary takes the value written first, from the other end.
     pub fn pop(&self) -> Option<T> {  
-        self.within_mutable_block(|l| l.pop())                                                                     
+        match self.rtype {
+            ReadAccessorType::Secondary => self.within_block(|l| l.pop()),
+            ReadAccessorType::Primary => self.within_block(|l| l.steal_one())
+        }
     }
 
     pub fn pop_count(&self, count:usize) -> Option<Vec<T>> {  
-        self.within_mutable_block(|l| l.pop_count(count))                                                                    
+        match self.rtype {
+            ReadAccessorType::Secondary => self.within_block(|l| l.pop_count(count)),
+            ReadAccessorType::Primary => self.within_block(|l| l.steal_count(count))
+        }
     }
 
     pub fn is_empty(&self) -> bool {
-        self.within_mutable_block(|l| Some(l.is_empty())).unwrap_or(true)
+        self.within_block(|l| Some(l.is_empty())).unwrap_or(true)
     }
 
     pub fn len(&self) -> usize {
-        self.within_mutable_block(|l| Some(l.val.len())).unwrap_or_default()        
+        self.within_block(|l| Some(l.len())).unwrap_or_default()        
     }
 
 
+    /// Only the primary writes, and only while the state is the default, that is while the runner is waiting.
+    /// Err is returned otherwise and the values are dropped.
     pub fn write(&self, values:Vec<T>) -> Result<bool,bool> {
-        self.within_mutable_block(|l| {
-            l.write(values);
-            Some(Ok(true))
-        }).unwrap_or(Err(false))               
+        self.within_writable(|l| l.write(values))
     }
-        
+
+    /// Only the primary replaces the values, and only while the state is the default, that is while the runner is
+    /// waiting. Err is returned otherwise and the values are dropped.
     pub fn replace(&self, values:Vec<T>) -> Result<bool,bool> { 
-        self.within_mutable_block(|l| {
-            l.replace(values);
-            Some(Ok(true))
-        }).unwrap_or(Err(false))                   
+        self.within_writable(|l| l.replace(values))
     }
 
-    pub fn is_write_blocked(&self) -> bool {
-        if let Some(obj) = self.get_ref() {
-            obj.is_write_blocked()
-        } else {
-            true
+    fn within_writable<F>(&self, f:F) -> Result<bool,bool>
+    where F: FnOnce(&LimitAccessQueue<T,State>) {
+        if !self.is_primary() {
+            return Err(false);
         }
+        self.within_block(|l| {
+            let waiting:u8 = State::default().into();
+            let current:u8 = l.get_state().into();
+            if current == waiting {
+                f(l);
+                Some(Ok(true))
+            } else {
+                Some(Err(false))
+            }
+        }).unwrap_or(Err(false))
     }
 
     pub fn steal(&mut self) -> Option<Vec<T>> {
@@ -205,7 +210,7 @@ where State: Clone + Default
             }
             ReadAccessorType::Primary => { 
                 
-                self.within_mutable_block(|l| l.steal())               
+                self.within_block(|l| l.steal())               
             }
         }
     }
@@ -216,20 +221,26 @@ where State: Clone + Default
                 None
             }
             ReadAccessorType::Primary => { 
-                self.within_mutable_block(|l| l.steal_half())               
+                self.within_block(|l| l.steal_half())               
             }
         }
     }
 
     pub fn set_state(&mut self, state:State) {
-        self.within_mutable_block(|l| {
+        self.within_block(|l| {
             l.set_state(state);
             Some(())
         });
     }
 
+    /// Sets the state to new only if it currently is the expected state.
+    pub fn compare_exchange_state(&mut self, current:State, new:State) -> Result<State,State> {
+        self.within_block(|l| Some(l.compare_exchange_state(current, new)))
+        .unwrap_or_else(|| Err(State::default()))
+    }
+
     pub fn state(&mut self) -> State {
-        self.within_mutable_block(|l| Some(l.get_state())).unwrap_or_default()        
+        self.within_block(|l| Some(l.get_state())).unwrap_or_default()        
     }
     
 }
\ No newline at end of file
@@ -0,0 +1,109 @@
+//! Collecting into a pre-allocated Vec or slice. Each worker thread writes the output of a value directly to the
+//! position of the value in the source, so the outputs are neither gathered per thread nor copied once joined.
+//! This needs a source of known length and a pipeline giving exactly one output per value, such as a chain of maps.
+//! Both are checked by the trait bounds, so other sources or pipelines do not compile.
+
+use std::marker::PhantomData;
+use crate::map::ParallelMap;
+use crate::pipeline::OneToOne;
+use crate::reduce::Reducer;
+use super::iterators::iterator::*;
+
+/// Start of the output buffer shared with the worker threads. Each index is written by only one thread.
+struct OutputPtr<T>(*mut T);
+
+unsafe impl<T: Send> Send for OutputPtr<T> {}
+unsafe impl<T: Send> Sync for OutputPtr<T> {}
+
+/// Writes each output to the position of its value and counts the outputs written
+struct WriteInto<T> {
+    ptr: OutputPtr<T>,
+    len: usize,
+    // Whether the positions hold values to be dropped when overwritten, as in a slice, or are uninitialised capacity
+    initialised: bool,
+    t: PhantomData<fn(T)>
+}
+
+impl<T: Send> Reducer<T> for WriteInto<T> {
+    type Acc = usize;
+
+    fn identity(&self) -> Self::Acc {
+        0
+    }
+
+    fn fold(&self, acc:Self::Acc, index:usize, value:T) -> Self::Acc {
+        assert!(index < self.len, "source gave more values than its length");
+        // SAFETY: the index is within the buffer, and each value of the source has a distinct index so that no
+        // two threads write the same position
+        unsafe {
+            let slot = self.ptr.0.add(index);
+            if self.initialised {
+                *slot = value;
+            } else {
+                slot.write(value);
+            }
+        }
+        acc + 1
+    }
+
+    fn combine(&self, left:Self::Acc, right:Self::Acc) -> Self::Acc {
+        left + right
+    }
+}
+
+/// Collecting into a Vec or slice on ParallelMap with a pipeline giving exactly one output per value.
+/// ```
+/// use parallel_task::prelude::*;
+///
+/// let vec = (0..10_000).collect::<Vec<u64>>();
+/// let mut out = Vec::with_capacity(10_000);
+/// vec.parallel_iter().map(|val| val * 2).collect_into_vec(&mut out);
+/// assert_eq!(out,(0..10_000).map(|val| val * 2).collect::<Vec<u64>>());
+///
+/// let mut buffer = [0u64; 100];
+/// (0..100u64).into_parallel_iter().map(|val| val * val).collect_into_slice(&mut buffer);
+/// assert_eq!(buffer[99],9_801)
+/// ```
+///
+/// A source whose length is not known up front may not be collected into a Vec.
+/// ```compile_fail
+/// use parallel_task::prelude::*;
+///
+/// let mut out = Vec::new();
+/// (0..100).par_bridge().map(|val| val * 2).collect_into_vec(&mut out);
+/// ```
+///
+impl<I,V,F,T> ParallelMap<V,F,T,I>
+where I:ExactSizeAtomicIterator<AtomicItem = V> + Send + Sized,
+F: OneToOne<V,Output=T>,
+V: Send + Sync,
+T:Send + Sync
+{
+    /// Collect the outputs into the Vec, replacing its contents. The Vec is grown only if its capacity is short of
+    /// the length of the source.
+    pub fn collect_into_vec(self, vec:&mut Vec<T>) {
+        let len = self.source_len();
+        vec.clear();
+        vec.reserve(len);
+        let written = self.write_into(vec.as_mut_ptr(), len, false);
+        assert_eq!(written, len, "source gave fewer values than its length");
+        // SAFETY: every position up to len has been written
+        unsafe { vec.set_len(len); }
+    }
+
+    /// Collect the outputs into the slice, whose length must be the same as that of the source
+    pub fn collect_into_slice(self, slice:&mut [T]) {
+        let len = self.source_len();
+        assert_eq!(slice.len(), len, "slice length does not match the length of the source");
+        let written = self.write_into(slice.as_mut_ptr(), len, true);
+        assert_eq!(written, len, "source gave fewer values than its length");
+    }
+
+    fn source_len(&self) -> usize {
+        self.iter.iter.exact_len()
+    }
+
+    fn write_into(self, ptr:*mut T, len:usize, initialised:bool) -> usize {
+        self.reduce_with(WriteInto { ptr: OutputPtr(ptr), len, initialised, t: PhantomData })
+    }
+}
@@ -1,18 +1,43 @@
 //! Collector is a trait that can be implemented across Collections and other types that implement
 //! Extend trait. For instance here it has been implemented for Vector, HashMap and so on. This allows
 //! the end result to collected in the desired form as per the annotation.
+//! Each worker thread builds its own partial collection for collections that can be merged, and the partial
+//! collections are merged in the order of the source once the threads are joined. The outputs of other collections
+//! are gathered in order and extended into a single collection.
+//! CollectTarget is what collect gathers the outputs in. It is implemented for every Collector, as well as for Result
+//! and Option of a CollectTarget.
 
 use std::hash::Hash;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
 
 pub trait Collector<T>:Extend<T> {
     fn initialize() -> Self;
+
+    /// Adds an output to the collection
+    fn push(&mut self, value:T) {
+        self.extend(std::iter::once(value));
+    }
+
+    /// Merges two partial collections, the left one holding the outputs that come first in the source. Collections
+    /// without a merge, which is the default, are not built per thread. Their outputs are instead gathered in order
+    /// and extended into a single collection.
+    fn merge() -> Option<fn(Self,Self) -> Self>
+    where Self: Sized {
+        None
+    }
 }
 
 impl<T> Collector<T> for Vec<T> {
     fn initialize() -> Self {
         Vec::new()
     }
+
+    fn merge() -> Option<fn(Self,Self) -> Self> {
+        Some(|mut left, mut right| {
+            left.append(&mut right);
+            left
+        })
+    }
 }
 
 impl<K,V> Collector<(K,V)> for HashMap<K,V>
@@ -23,11 +48,265 @@ where K: Eq + Hash + Send,
         let hsh:HashMap<K,V> = HashMap::new();
         hsh
     }
+
+    fn merge() -> Option<fn(Self,Self) -> Self> {
+        Some(|mut left, right| {
+            left.extend(right);
+            left
+        })
+    }
 }
 
 impl<T> Collector<T> for VecDeque<T> {
     fn initialize() -> Self {
         VecDeque::new()
     }
+
+    fn merge() -> Option<fn(Self,Self) -> Self> {
+        Some(|mut left, mut right| {
+            left.append(&mut right);
+            left
+        })
+    }
+}
+
+impl<T> Collector<T> for HashSet<T>
+where T: Eq + Hash
+{
+    fn initialize() -> Self {
+        HashSet::new()
+    }
+
+    fn merge() -> Option<fn(Self,Self) -> Self> {
+        Some(|mut left, right| {
+            left.extend(right);
+            left
+        })
+    }
+}
+
+impl<K,V> Collector<(K,V)> for BTreeMap<K,V>
+where K: Ord + Send,
+    V: Send,
+{
+    fn initialize() -> Self {
+        BTreeMap::new()
+    }
+
+    fn merge() -> Option<fn(Self,Self) -> Self> {
+        Some(|mut left, mut right| {
+            left.append(&mut right);
+            left
+        })
+    }
 }
 
+impl<T> Collector<T> for BTreeSet<T>
+where T: Ord
+{
+    fn initialize() -> Self {
+        BTreeSet::new()
+    }
+
+    fn merge() -> Option<fn(Self,Self) -> Self> {
+        Some(|mut left, mut right| {
+            left.append(&mut right);
+            left
+        })
+    }
+}
+
+impl<T> Collector<T> for BinaryHeap<T>
+where T: Ord
+{
+    fn initialize() -> Self {
+        BinaryHeap::new()
+    }
+
+    fn merge() -> Option<fn(Self,Self) -> Self> {
+        Some(|mut left, mut right| {
+            left.append(&mut right);
+            left
+        })
+    }
+}
+
+impl<T> Collector<T> for LinkedList<T> {
+    fn initialize() -> Self {
+        LinkedList::new()
+    }
+
+    fn merge() -> Option<fn(Self,Self) -> Self> {
+        Some(|mut left, mut right| {
+            left.append(&mut right);
+            left
+        })
+    }
+}
+
+impl Collector<char> for String {
+    fn initialize() -> Self {
+        String::new()
+    }
+
+    fn merge() -> Option<fn(Self,Self) -> Self> {
+        Some(|mut left, right| {
+            left.push_str(&right);
+            left
+        })
+    }
+}
+
+impl Collector<&str> for String {
+    fn initialize() -> Self {
+        String::new()
+    }
+
+    fn merge() -> Option<fn(Self,Self) -> Self> {
+        Some(|mut left, right| {
+            left.push_str(&right);
+            left
+        })
+    }
+}
+
+impl Collector<String> for String {
+    fn initialize() -> Self {
+        String::new()
+    }
+
+    fn merge() -> Option<fn(Self,Self) -> Self> {
+        Some(|mut left, right| {
+            left.push_str(&right);
+            left
+        })
+    }
+}
+
+/// Partial collection built by a run of outputs. The outputs go into the collection if it can be merged, else they
+/// are kept in order till the collection is finished.
+pub struct Partial<C,T> {
+    collection: C,
+    outputs: Vec<T>
+}
+
+/// Type that collect may gather the outputs in. The outputs are pushed into partial collections, which are merged in
+/// the order of the source and then finished into Self.
+pub trait CollectTarget<T>: Sized {
+    type Partial: Send;
+
+    fn partial() -> Self::Partial;
+
+    /// Adds an output to the partial collection
+    fn push(partial:&mut Self::Partial, value:T);
+
+    /// Merges two partial collections, the left one holding the outputs that come first in the source
+    fn merge(left:Self::Partial, right:Self::Partial) -> Self::Partial;
+
+    fn finish(partial:Self::Partial) -> Self;
+
+    /// Check on each output after which the remaining outputs are not needed. Once an output satisfies it,
+    /// the values after the one giving the output are skipped.
+    fn halt() -> Option<fn(&T) -> bool> {
+        None
+    }
+}
+
+impl<T,C> CollectTarget<T> for C
+where C: Collector<T> + Send,
+    T: Send,
+{
+    type Partial = Partial<C,T>;
+
+    fn partial() -> Self::Partial {
+        Partial { collection: C::initialize(), outputs: Vec::new() }
+    }
+
+    fn push(partial:&mut Self::Partial, value:T) {
+        if C::merge().is_some() {
+            partial.collection.push(value);
+        } else {
+            partial.outputs.push(value);
+        }
+    }
+
+    fn merge(mut left:Self::Partial, mut right:Self::Partial) -> Self::Partial {
+        match C::merge() {
+            Some(merge) => Partial { collection: merge(left.collection, right.collection), outputs: Vec::new() },
+            None => {
+                left.outputs.append(&mut right.outputs);
+                left
+            }
+        }
+    }
+
+    fn finish(mut partial:Self::Partial) -> Self {
+        partial.collection.extend(partial.outputs);
+        partial.collection
+    }
+}
+
+/// Collects the Ok values, or gives the first Err in the order of the source. The values after an Err are skipped.
+impl<T,E,C> CollectTarget<Result<T,E>> for Result<C,E>
+where C: CollectTarget<T>,
+    E: Send,
+{
+    type Partial = Result<C::Partial,E>;
+
+    fn partial() -> Self::Partial {
+        Ok(C::partial())
+    }
+
+    fn push(partial:&mut Self::Partial, value:Result<T,E>) {
+        if let Ok(collection) = partial {
+            match value {
+                Ok(value) => C::push(collection, value),
+                Err(e) => *partial = Err(e)
+            }
+        }
+    }
+
+    fn merge(left:Self::Partial, right:Self::Partial) -> Self::Partial {
+        Ok(C::merge(left?, right?))
+    }
+
+    fn finish(partial:Self::Partial) -> Self {
+        partial.map(C::finish)
+    }
+
+    fn halt() -> Option<fn(&Result<T,E>) -> bool> {
+        Some(Result::is_err)
+    }
+}
+
+/// Collects the Some values, or gives None if any output is None. The values after a None are skipped.
+impl<T,C> CollectTarget<Option<T>> for Option<C>
+where C: CollectTarget<T>
+{
+    type Partial = Option<C::Partial>;
+
+    fn partial() -> Self::Partial {
+        Some(C::partial())
+    }
+
+    fn push(partial:&mut Self::Partial, value:Option<T>) {
+        if let Some(collection) = partial {
+            match value {
+                Some(value) => C::push(collection, value),
+                None => *partial = None
+            }
+        }
+    }
+
+    fn merge(left:Self::Partial, right:Self::Partial) -> Self::Partial {
+        Some(C::merge(left?, right?))
+    }
+
+    fn finish(partial:Self::Partial) -> Self {
+        partial.map(C::finish)
+    }
+
+    fn halt() -> Option<fn(&Option<T>) -> bool> {
+        Some(Option::is_none)
+    }
+}
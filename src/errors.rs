@@ -1,3 +1,4 @@
+use std::any::Any;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,6 +7,27 @@ pub enum WorkThreadError {
     ThreadJoin, 
     #[error("thread add error : {0}")] 
     ThreadAdd(String),  
+    #[error("the global thread pool has already been initialised")]
+    GlobalPoolInitialised,
+    /// A user function panicked within a worker. Carries the original panic payload, the name of the worker
+    /// and the index of the value being processed if known.
+    #[error("worker {worker} panicked{}: {}", .index.map(|idx| format!(" at index {}",idx)).unwrap_or_default(), panic_message(.payload.as_ref()))]
+    WorkerPanic {
+        worker: String,
+        index: Option<usize>,
+        payload: Box<dyn Any + Send + 'static>
+    },
     #[error("other error - {0}")]
     Other(String),
-}
\ No newline at end of file
+}
+
+/// Gives the message within a panic payload, which is usually a &str or a String.
+pub fn panic_message(payload:&(dyn Any + Send)) -> &str {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg
+    } else {
+        "Box<dyn Any>"
+    }
+}
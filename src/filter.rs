@@ -0,0 +1,56 @@
+//! ParallelFilterIter and ParallelFilterMapIter allow filter and filter_map to be called directly on an AtomicIterator.
+//! They give a ParallelMap, so that further map, filter and flat_map adapters are chained within the same parallel pass.
+
+use crate::map::ParallelMap;
+use crate::pipeline::{Filter, FilterMap, Identity};
+use super::iterators::iterator::*;
+
+/// ParallelFilterIter allows calling the .filter(predicate) on type implementing AtomicIterator to get a ParallelMap object
+/// holding only the values for which the predicate is true.
+/// ```
+/// use parallel_task::prelude::*;
+/// 
+/// let res = (0..100_000).collect::<Vec<i32>>().parallel_iter().filter(|val|**val % 2 == 0).map(|val|*val).collect::<Vec<i32>>();
+/// assert_eq!(res,(0..100_000).filter(|val|val % 2 == 0).collect::<Vec<i32>>())
+/// ```
+/// 
+pub trait ParallelFilterIter<I, V,P>
+where Self: AtomicIterator<AtomicItem = V> + Send + Sized,
+P: Fn(&V) -> bool + Send + Sync,
+V: Send + Sync
+{
+    fn filter(self,predicate:P) -> ParallelMap<V,Filter<Identity<V>,P>,V,Self>{
+        ParallelMap::new(self,Filter::new(Identity::default(),predicate))
+    }
+}
+
+impl<I,V,P> ParallelFilterIter<I, V,P> for I 
+where I: AtomicIterator<AtomicItem = V> + Send + Sized,
+P: Fn(&V) -> bool + Send + Sync,
+V: Send + Sync {}
+
+/// ParallelFilterMapIter allows calling the .filter_map(f) on type implementing AtomicIterator to get a ParallelMap object
+/// holding the Some values returned by f.
+/// ```
+/// use parallel_task::prelude::*;
+/// 
+/// let res = vec!["1","x","3"].parallel_iter().filter_map(|val|val.parse::<i32>().ok()).collect::<Vec<i32>>();
+/// assert_eq!(res,vec![1,3])
+/// ```
+/// 
+pub trait ParallelFilterMapIter<I, V,F,T>
+where Self: AtomicIterator<AtomicItem = V> + Send + Sized,
+F: Fn(V) -> Option<T> + Send + Sync,
+V: Send + Sync,
+T:Send + Sync
+{
+    fn filter_map(self,f:F) -> ParallelMap<V,FilterMap<Identity<V>,F>,T,Self>{
+        ParallelMap::new(self,FilterMap::new(Identity::default(),f))
+    }
+}
+
+impl<I,V,F,T> ParallelFilterMapIter<I, V,F,T> for I 
+where I: AtomicIterator<AtomicItem = V> + Send + Sized,
+F: Fn(V) -> Option<T> + Send + Sync,
+V: Send + Sync,
+T:Send + Sync {}
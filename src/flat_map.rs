@@ -0,0 +1,34 @@
+//! ParallelFlatMapIter allows flat_map to be called directly on an AtomicIterator. Every item of the IntoIterator returned
+//! for a value is collected at the position of that value, in the order the items are given.
+
+use crate::map::ParallelMap;
+use crate::pipeline::{FlatMap, Identity};
+use super::iterators::iterator::*;
+
+/// ParallelFlatMapIter allows calling the .flat_map(f) on type implementing AtomicIterator to get a ParallelMap object
+/// holding the flattened outputs of f.
+/// ```
+/// use parallel_task::prelude::*;
+/// 
+/// let res = vec![1,2,3].parallel_iter().flat_map(|val|0..*val).collect::<Vec<usize>>();
+/// assert_eq!(res,vec![0,0,1,0,1,2])
+/// ```
+/// 
+pub trait ParallelFlatMapIter<I, V,F,U>
+where Self: AtomicIterator<AtomicItem = V> + Send + Sized,
+F: Fn(V) -> U + Send + Sync,
+U: IntoIterator,
+U::Item: Send + Sync,
+V: Send + Sync
+{
+    fn flat_map(self,f:F) -> ParallelMap<V,FlatMap<Identity<V>,F>,U::Item,Self>{
+        ParallelMap::new(self,FlatMap::new(Identity::default(),f))
+    }
+}
+
+impl<I,V,F,U> ParallelFlatMapIter<I, V,F,U> for I 
+where I: AtomicIterator<AtomicItem = V> + Send + Sized,
+F: Fn(V) -> U + Send + Sync,
+U: IntoIterator,
+U::Item: Send + Sync,
+V: Send + Sync {}
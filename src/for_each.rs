@@ -3,8 +3,9 @@
 //! Fetch.
 
 use std::marker::PhantomData;
+use crate::map::ParallelMap;
 use crate::task_queue::TaskQueue;
-use crate::utils;
+use crate::push_workers::thread_pool::ThreadPool;
 use crate::worker_thread::WorkerThreads;
 use super::iterators::iterator::AtomicIterator;
 
@@ -32,6 +33,37 @@ F: Fn(V) + Send + Sync,
 V: Send + Sync,
 {}
 
+/// ParallelTryForEachIter allows calling the .try_for_each(f) to run a fallible Fn function on type implementing AtomicIterator.
+/// The earliest Err in the order of the source is returned. Once an Err is found the values after it are skipped by
+/// all the threads, though those already being processed may still finish. The values before it are all processed.
+/// ```
+/// use parallel_task::prelude::*;
+/// 
+/// let res = (0..100_000).collect::<Vec<i32>>().parallel_iter()
+/// .try_for_each(|val| if *val < 10 { Ok(()) } else { Err(*val) });
+/// assert!(res.is_err())
+/// ```
+/// 
+pub trait ParallelTryForEachIter<I, V,F,E>
+where Self: AtomicIterator<AtomicItem = V> + Send + Sized,
+F: Fn(V) -> Result<(),E> + Send + Sync,
+V: Send + Sync,
+E: Send + Sync,
+{
+    fn try_for_each(self,f:F) -> Result<(),E> {
+        ParallelMap::new(self,f)
+        .try_collect::<Vec<()>>()
+        .map(|_|())
+    }
+}
+
+impl<I,V,F,E> ParallelTryForEachIter<I, V,F,E> for I 
+where I: AtomicIterator<AtomicItem = V> + Send + Sized,
+F: Fn(V) -> Result<(),E> + Send + Sync,
+V: Send + Sync,
+E: Send + Sync,
+{}
+
 /// ParallelForEach is a structure type that captures the information necessary to run the values within the Iterator in parallel
 /// Its the result of parallel_task that can be run on any Iterator implementing type.
 /// ```
@@ -49,6 +81,7 @@ V: Send
     pub iter: TaskQueue<I,V>,
     pub f:F,
     pub num_threads:usize,
+    pub pool: ThreadPool,
     pub v: PhantomData<V>,    
 }
 
@@ -63,19 +96,26 @@ V: Send + Sync,
         Self {
             iter: TaskQueue { iter },
             f,
-            num_threads: Self::max_threads(),
+            num_threads: ThreadPool::global().max_threads(),
+            pool: ThreadPool::global().clone(),
             v: PhantomData,            
         }
     }
 
-    fn max_threads() -> usize {
-        // available_parallelism() function gives an idea of the CPUs available 
-        utils::max_threads()
+    /// Set the maximum number of threads used for running the parallel tasker. This is capped to the size of the pool.
+    pub fn threads(mut self, nthreads:usize) -> Self {
+        self.num_threads = usize::min(self.pool.max_threads(),nthreads);
+        self
     }
 
-    /// Set the thread pool size for running the parallel tasker    
-    pub fn threads(mut self, nthreads:usize) -> Self {
-        self.num_threads = usize::min(Self::max_threads(),nthreads);
+    /// Run the tasks on the given pool instead of the global pool.
+    pub fn pool(mut self, pool:&ThreadPool) -> Self {
+        self.num_threads = if self.num_threads == self.pool.max_threads() {
+            pool.max_threads()
+        } else {
+            usize::min(self.num_threads,pool.max_threads())
+        };
+        self.pool = pool.clone();
         self
     }
         
@@ -83,7 +123,7 @@ V: Send + Sync,
     {                
         let num_threads = self.num_threads;        
 
-        WorkerThreads { nthreads: num_threads }
+        WorkerThreads { nthreads: num_threads, pool: self.pool.clone() }
         .run(self)      
         
     }
@@ -0,0 +1,433 @@
+//! Enumerate, Zip and Chain combine sources, and Take, Skip and StepBy select the values of a source, before they
+//! are run in parallel. Sources over Vec, slices and ranges are instead narrowed in place by take, skip and step_by,
+//! so that the values left out are never pulled. The adapters here wrap the AtomicIterator of the source,
+//! so that the values are paired up or tagged with their index as they are pulled by the WorkerController, and the
+//! adapters of ParallelMap may be chained on them as on any other source.
+
+use std::collections::VecDeque;
+use crate::map::ParallelMap;
+use crate::pipeline::{Identity, TakeWhileAny};
+use super::iterators::iterator::*;
+
+/// Tags each value with its position in the source
+pub struct Enumerate<I> {
+    iter: I,
+    count: usize
+}
+
+impl<I> AtomicIterator for Enumerate<I>
+where I: AtomicIterator
+{
+    type AtomicItem = (usize, I::AtomicItem);
+
+    fn atomic_next(&mut self) -> Option<Self::AtomicItem> {
+        let value = self.iter.atomic_next()?;
+        self.count += 1;
+        Some((self.count - 1, value))
+    }
+
+    fn atomic_pull(&mut self) -> Option<Vec<Self::AtomicItem>> {
+        let values = self.iter.atomic_pull()?;
+        let start = self.count;
+        self.count += values.len();
+        Some((start..).zip(values).collect())
+    }
+
+    fn len(&self) -> Option<usize> {
+        self.iter.len()
+    }
+
+    fn is_active(&self) -> bool {
+        self.iter.is_active()
+    }
+
+    fn is_pending(&self) -> bool {
+        self.iter.is_pending()
+    }
+}
+
+impl<I> ExactSizeAtomicIterator for Enumerate<I>
+where I: ExactSizeAtomicIterator
+{
+    fn exact_len(&self) -> usize {
+        self.iter.exact_len()
+    }
+}
+
+/// Pairs up the values of two sources in order. It stops when either source runs out, as with `Iterator::zip`, so
+/// the values left in the longer source are deliberately left out. Its length is that of the shorter source.
+pub struct Zip<A,B>
+where A: AtomicIterator,
+B: AtomicIterator
+{
+    a: A,
+    b: B,
+    // Values pulled from one source ahead of the other
+    a_buf: VecDeque<A::AtomicItem>,
+    b_buf: VecDeque<B::AtomicItem>,
+    done: bool
+}
+
+impl<A,B> Zip<A,B>
+where A: AtomicIterator,
+B: AtomicIterator
+{
+    /// Pulls from whichever source has no values buffered, till both have some or either runs out
+    fn fill(&mut self) {
+        while !self.done && (self.a_buf.is_empty() || self.b_buf.is_empty()) {
+            let pulled = if self.a_buf.is_empty() {
+                self.a.atomic_pull().map(|values| self.a_buf.extend(values))
+            } else {
+                self.b.atomic_pull().map(|values| self.b_buf.extend(values))
+            };
+            self.done = pulled.is_none();
+        }
+    }
+}
+
+impl<A,B> AtomicIterator for Zip<A,B>
+where A: AtomicIterator,
+B: AtomicIterator
+{
+    type AtomicItem = (A::AtomicItem, B::AtomicItem);
+
+    fn atomic_next(&mut self) -> Option<Self::AtomicItem> {
+        self.fill();
+        Some((self.a_buf.pop_front()?, self.b_buf.pop_front()?))
+    }
+
+    fn atomic_pull(&mut self) -> Option<Vec<Self::AtomicItem>> {
+        self.fill();
+        let size = usize::min(self.a_buf.len(), self.b_buf.len());
+        if size == 0 {
+            None
+        } else {
+            Some(self.a_buf.drain(..size).zip(self.b_buf.drain(..size)).collect())
+        }
+    }
+
+    fn len(&self) -> Option<usize> {
+        let a = self.a.len()? + self.a_buf.len();
+        let b = self.b.len()? + self.b_buf.len();
+        Some(usize::min(a, b))
+    }
+
+    fn is_active(&self) -> bool {
+        !self.done || (!self.a_buf.is_empty() && !self.b_buf.is_empty())
+    }
+
+    fn is_pending(&self) -> bool {
+        self.a.is_pending() || self.b.is_pending()
+    }
+}
+
+impl<A,B> ExactSizeAtomicIterator for Zip<A,B>
+where A: ExactSizeAtomicIterator,
+B: ExactSizeAtomicIterator
+{
+    fn exact_len(&self) -> usize {
+        let a = self.a.exact_len() + self.a_buf.len();
+        let b = self.b.exact_len() + self.b_buf.len();
+        usize::min(a, b)
+    }
+}
+
+/// Runs the values of the first source followed by those of the second
+pub struct Chain<A,B> {
+    a: A,
+    b: B,
+    a_done: bool
+}
+
+impl<A,B> AtomicIterator for Chain<A,B>
+where A: AtomicIterator,
+B: AtomicIterator<AtomicItem = A::AtomicItem>
+{
+    type AtomicItem = A::AtomicItem;
+
+    fn atomic_next(&mut self) -> Option<Self::AtomicItem> {
+        if !self.a_done {
+            match self.a.atomic_next() {
+                Some(value) => return Some(value),
+                None => self.a_done = true
+            }
+        }
+        self.b.atomic_next()
+    }
+
+    fn atomic_pull(&mut self) -> Option<Vec<Self::AtomicItem>> {
+        if !self.a_done {
+            match self.a.atomic_pull() {
+                Some(values) => return Some(values),
+                None => self.a_done = true
+            }
+        }
+        self.b.atomic_pull()
+    }
+
+    fn len(&self) -> Option<usize> {
+        let a = if self.a_done { 0 } else { self.a.len()? };
+        Some(a + self.b.len()?)
+    }
+
+    fn is_active(&self) -> bool {
+        (!self.a_done && self.a.is_active()) || self.b.is_active()
+    }
+
+    // The second source is only pulled from once the first is done, so there is always a pull after the first
+    fn is_pending(&self) -> bool {
+        true
+    }
+}
+
+impl<A,B> ExactSizeAtomicIterator for Chain<A,B>
+where A: ExactSizeAtomicIterator,
+B: ExactSizeAtomicIterator<AtomicItem = A::AtomicItem>
+{
+    fn exact_len(&self) -> usize {
+        let a = if self.a_done { 0 } else { self.a.exact_len() };
+        a + self.b.exact_len()
+    }
+}
+
+/// Gives only the first n values of the source
+pub struct Take<I> {
+    iter: I,
+    remaining: usize
+}
+
+impl<I> AtomicIterator for Take<I>
+where I: AtomicIterator
+{
+    type AtomicItem = I::AtomicItem;
+
+    fn atomic_next(&mut self) -> Option<Self::AtomicItem> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let value = self.iter.atomic_next()?;
+        self.remaining -= 1;
+        Some(value)
+    }
+
+    fn atomic_pull(&mut self) -> Option<Vec<Self::AtomicItem>> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let mut values = self.iter.atomic_pull()?;
+        values.truncate(self.remaining);
+        self.remaining -= values.len();
+        Some(values)
+    }
+
+    fn len(&self) -> Option<usize> {
+        Some(usize::min(self.iter.len()?, self.remaining))
+    }
+
+    fn is_active(&self) -> bool {
+        self.remaining > 0 && self.iter.is_active()
+    }
+
+    fn is_pending(&self) -> bool {
+        self.iter.is_pending()
+    }
+}
+
+impl<I> ExactSizeAtomicIterator for Take<I>
+where I: ExactSizeAtomicIterator
+{
+    fn exact_len(&self) -> usize {
+        usize::min(self.iter.exact_len(), self.remaining)
+    }
+}
+
+/// Leaves out the first n values of the source
+pub struct Skip<I> {
+    iter: I,
+    to_skip: usize
+}
+
+impl<I> AtomicIterator for Skip<I>
+where I: AtomicIterator
+{
+    type AtomicItem = I::AtomicItem;
+
+    fn atomic_next(&mut self) -> Option<Self::AtomicItem> {
+        while self.to_skip > 0 {
+            self.iter.atomic_next()?;
+            self.to_skip -= 1;
+        }
+        self.iter.atomic_next()
+    }
+
+    fn atomic_pull(&mut self) -> Option<Vec<Self::AtomicItem>> {
+        loop {
+            let mut values = self.iter.atomic_pull()?;
+            if self.to_skip < values.len() {
+                let values = values.split_off(self.to_skip);
+                self.to_skip = 0;
+                return Some(values);
+            }
+            self.to_skip -= values.len();
+        }
+    }
+
+    fn len(&self) -> Option<usize> {
+        Some(self.iter.len()?.saturating_sub(self.to_skip))
+    }
+
+    fn is_active(&self) -> bool {
+        self.iter.is_active()
+    }
+
+    fn is_pending(&self) -> bool {
+        self.iter.is_pending()
+    }
+}
+
+impl<I> ExactSizeAtomicIterator for Skip<I>
+where I: ExactSizeAtomicIterator
+{
+    fn exact_len(&self) -> usize {
+        self.iter.exact_len().saturating_sub(self.to_skip)
+    }
+}
+
+/// Gives the first value of the source and every step-th value after it
+pub struct StepBy<I> {
+    iter: I,
+    step: usize,
+    // Position of the next value of the source within its step
+    phase: usize
+}
+
+impl<I> AtomicIterator for StepBy<I>
+where I: AtomicIterator
+{
+    type AtomicItem = I::AtomicItem;
+
+    fn atomic_next(&mut self) -> Option<Self::AtomicItem> {
+        loop {
+            let value = self.iter.atomic_next()?;
+            let keep = self.phase == 0;
+            self.phase = (self.phase + 1) % self.step;
+            if keep {
+                return Some(value);
+            }
+        }
+    }
+
+    fn atomic_pull(&mut self) -> Option<Vec<Self::AtomicItem>> {
+        loop {
+            let values = self.iter.atomic_pull()?;
+            let phase = self.phase;
+            self.phase = (phase + values.len()) % self.step;
+            let values = values.into_iter()
+            .enumerate()
+            .filter(|(pos,_)| (phase + pos).is_multiple_of(self.step))
+            .map(|(_,value)| value)
+            .collect::<Vec<Self::AtomicItem>>();
+            if !values.is_empty() {
+                return Some(values);
+            }
+        }
+    }
+
+    fn len(&self) -> Option<usize> {
+        let skipped = (self.step - self.phase) % self.step;
+        Some(self.iter.len()?.saturating_sub(skipped).div_ceil(self.step))
+    }
+
+    fn is_active(&self) -> bool {
+        self.iter.is_active()
+    }
+
+    fn is_pending(&self) -> bool {
+        self.iter.is_pending()
+    }
+}
+
+impl<I> ExactSizeAtomicIterator for StepBy<I>
+where I: ExactSizeAtomicIterator
+{
+    fn exact_len(&self) -> usize {
+        let skipped = (self.step - self.phase) % self.step;
+        self.iter.exact_len().saturating_sub(skipped).div_ceil(self.step)
+    }
+}
+
+/// Values of a source run while the predicate holds
+pub type ParallelTakeWhileAny<I,P> = ParallelMap<<I as AtomicIterator>::AtomicItem,TakeWhileAny<Identity<<I as AtomicIterator>::AtomicItem>,P>,<I as AtomicIterator>::AtomicItem,I>;
+
+/// ParallelIndexedIter allows the values of a source to be enumerated, or sources to be zipped or chained, before
+/// the adapters of ParallelMap are called on them.
+/// ```
+/// use parallel_task::prelude::*;
+///
+/// let names = vec!["a","b","c"];
+/// let scores = vec![10,20,30];
+/// let res = names.parallel_iter().zip(scores.parallel_iter()).enumerate()
+/// .map(|(idx,(name,score))| format!("{}{}{}",idx,name,score))
+/// .collect::<Vec<String>>();
+/// assert_eq!(res,vec!["0a10","1b20","2c30"]);
+///
+/// let res = (0..3).into_parallel_iter().chain((10..13).into_parallel_iter()).map(|val| val).collect::<Vec<i32>>();
+/// assert_eq!(res,vec![0,1,2,10,11,12]);
+///
+/// let res = (0..100).into_parallel_iter().skip(10).step_by(20).take(3).map(|val| val).collect::<Vec<i32>>();
+/// assert_eq!(res,vec![10,30,50])
+/// ```
+///
+pub trait ParallelIndexedIter
+where Self: AtomicIterator + Send + Sized
+{
+    /// Tags each value with its position in the source
+    fn enumerate(self) -> Enumerate<Self> {
+        Enumerate { iter: self, count: 0 }
+    }
+
+    /// Pairs up the values with those of the other source. It stops when either runs out, and the values left in the
+    /// longer source are dropped without being run, as with `Iterator::zip`.
+    fn zip<B>(self, other:B) -> Zip<Self,B>
+    where B: AtomicIterator
+    {
+        Zip { a: self, b: other, a_buf: VecDeque::new(), b_buf: VecDeque::new(), done: false }
+    }
+
+    /// Runs the values of the other source after those of this one
+    fn chain<B>(self, other:B) -> Chain<Self,B>
+    where B: AtomicIterator<AtomicItem = Self::AtomicItem>
+    {
+        Chain { a: self, b: other, a_done: false }
+    }
+
+    /// Gives only the first n values. The source is pulled from till n values are given, and the values pulled past
+    /// them are dropped.
+    fn take(self, n:usize) -> Take<Self> {
+        Take { iter: self, remaining: n }
+    }
+
+    /// Leaves out the first n values, which are still pulled from the source
+    fn skip(self, n:usize) -> Skip<Self> {
+        Skip { iter: self, to_skip: n }
+    }
+
+    /// Gives the first value and every step-th value after it, out of all the values pulled from the source. Panics
+    /// if step is 0, as with `Iterator::step_by`.
+    fn step_by(self, step:usize) -> StepBy<Self> {
+        assert!(step != 0, "step must not be zero");
+        StepBy { iter: self, step, phase: 0 }
+    }
+
+    /// Runs the values while the predicate holds. Once it fails for a value, all the threads are stopped. The values
+    /// run by then that satisfy the predicate are kept, which need not be all of those before the failing value.
+    fn take_while_any<P>(self, predicate:P) -> ParallelTakeWhileAny<Self,P>
+    where Self::AtomicItem: Send + Sync,
+    P: Fn(&Self::AtomicItem) -> bool + Send + Sync
+    {
+        ParallelMap::new(self, Identity::default()).take_while_any(predicate)
+    }
+}
+
+impl<I> ParallelIndexedIter for I
+where I: AtomicIterator + Send + Sized {}
@@ -0,0 +1,80 @@
+//! IterBridge allows any serial Iterator to be used as a parallel source. The length of such iterators is not known
+//! up front, so the values are pulled in batches that grow as the iterator keeps giving values, and the WorkerController
+//! keeps pulling batches for the free threads until the iterator is exhausted.
+
+use crate::iterators::prelude::{DiscreteQueue, ParallelIterator};
+
+const MIN_BATCH:usize = 16;
+const MAX_BATCH:usize = 1024;
+
+pub struct IterBridge<I>
+where I: Iterator
+{
+    iter:I,
+    batch:usize,
+    exhausted:bool
+}
+
+impl<I> IterBridge<I>
+where I: Iterator
+{
+    pub fn new(iter:I) -> Self {
+        Self {
+            iter,
+            batch:MIN_BATCH,
+            exhausted:false
+        }
+    }
+}
+
+impl<I> DiscreteQueue for IterBridge<I>
+where I: Iterator
+{
+    type Output = I::Item;
+
+    fn pop(&mut self) -> Option<Self::Output> {
+        let val = if self.exhausted { None } else { self.iter.next() };
+        self.exhausted = val.is_none();
+        val
+    }
+
+    fn pull(&mut self) -> Option<Vec<Self::Output>> {
+        // The iterator may not be fused, so it is not called again once it has given None
+        if self.exhausted {
+            return None;
+        }
+        let res = self.iter.by_ref().take(self.batch).collect::<Vec<Self::Output>>();
+        // Batches double in size so that long iterators need fewer pulls
+        self.exhausted = res.len() < self.batch;
+        self.batch = usize::min(self.batch * 2, MAX_BATCH);
+        if res.is_empty() { None } else { Some(res) }
+    }
+
+    fn is_active(&self) -> bool {
+        !self.exhausted
+    }
+
+    fn len(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// IntoParallelBridge turns any serial Iterator, such as the lines of a reader or a generated sequence,
+/// into a parallel source. The outputs are collected in the order of the iterator.
+/// ```
+/// use parallel_task::prelude::*;
+///
+/// let res = (0..10_000).filter(|val| val % 3 == 0).par_bridge().map(|val| val * 2).collect::<Vec<i32>>();
+/// assert_eq!(res,(0..10_000).filter(|val| val % 3 == 0).map(|val| val * 2).collect::<Vec<i32>>())
+/// ```
+pub trait IntoParallelBridge: Iterator + Sized {
+    fn par_bridge(self) -> ParallelIterator<IterBridge<Self>,Self::Item>;
+}
+
+impl<I> IntoParallelBridge for I
+where I: Iterator
+{
+    fn par_bridge(self) -> ParallelIterator<IterBridge<Self>,Self::Item> {
+        ParallelIterator::new(IterBridge::new(self))
+    }
+}
@@ -0,0 +1,86 @@
+//! Chunked parallel iteration over slices and Vec. Each value handed to the threads is a sub-slice, so that a closure
+//! works on a contiguous block at a time. The chunks are the values of the source, so they are moved between threads
+//! whole when the work is redistributed.
+
+use std::slice;
+use crate::iterators::prelude::ParallelIterator;
+use crate::iterators::queued::SizedQueue;
+
+/// Chunks of a slice, run in parallel
+pub type ParallelChunks<'data,T> = ParallelIterator<SizedQueue<slice::Chunks<'data,T>,&'data [T]>,&'data [T]>;
+
+/// Chunks of the exact size of a slice, run in parallel
+pub type ParallelChunksExact<'data,T> = ParallelIterator<SizedQueue<slice::ChunksExact<'data,T>,&'data [T]>,&'data [T]>;
+
+/// Overlapping windows of a slice, run in parallel
+pub type ParallelWindows<'data,T> = ParallelIterator<SizedQueue<slice::Windows<'data,T>,&'data [T]>,&'data [T]>;
+
+/// Mutable chunks of a slice, run in parallel
+pub type ParallelChunksMut<'data,T> = ParallelIterator<SizedQueue<slice::ChunksMut<'data,T>,&'data mut [T]>,&'data mut [T]>;
+
+/// ParallelSlice gives sub-slices of a Vec or slice as the values to be run in parallel.
+/// ```
+/// use parallel_task::prelude::*;
+///
+/// let vec = (1..=10).collect::<Vec<i32>>();
+/// let res = vec.par_chunks(4).map(|chunk| chunk.iter().sum::<i32>()).collect::<Vec<i32>>();
+/// assert_eq!(res,vec![10,26,19]);
+/// let res = vec.par_windows(2).map(|pair| pair[1] - pair[0]).collect::<Vec<i32>>();
+/// assert_eq!(res,vec![1;9])
+/// ```
+pub trait ParallelSlice<'data,T> {
+    /// Chunks of size elements, the last of which may be shorter, as with `slice::chunks`
+    fn par_chunks(&'data self, size:usize) -> ParallelChunks<'data,T>;
+
+    /// Chunks of exactly size elements. The remainder left at the end is not run, as with `slice::chunks_exact`.
+    fn par_chunks_exact(&'data self, size:usize) -> ParallelChunksExact<'data,T>;
+
+    /// Overlapping windows of size elements, as with `slice::windows`
+    fn par_windows(&'data self, size:usize) -> ParallelWindows<'data,T>;
+}
+
+impl<'data,T> ParallelSlice<'data,T> for [T]
+where T: 'data
+{
+    fn par_chunks(&'data self, size:usize) -> ParallelChunks<'data,T> {
+        let chunks = self.chunks(size);
+        let len = chunks.len();
+        ParallelIterator::new(SizedQueue::new(chunks, len))
+    }
+
+    fn par_chunks_exact(&'data self, size:usize) -> ParallelChunksExact<'data,T> {
+        let chunks = self.chunks_exact(size);
+        let len = chunks.len();
+        ParallelIterator::new(SizedQueue::new(chunks, len))
+    }
+
+    fn par_windows(&'data self, size:usize) -> ParallelWindows<'data,T> {
+        let windows = self.windows(size);
+        let len = windows.len();
+        ParallelIterator::new(SizedQueue::new(windows, len))
+    }
+}
+
+/// ParallelSliceMut gives mutable sub-slices of a Vec or slice as the values to be run in parallel.
+/// Each chunk is handed to exactly one thread.
+/// ```
+/// use parallel_task::prelude::*;
+///
+/// let mut vec = vec![1; 10];
+/// vec.par_chunks_mut(3).for_each(|chunk| chunk.iter_mut().enumerate().for_each(|(idx,val)| *val += idx as i32));
+/// assert_eq!(vec,vec![1,2,3,1,2,3,1,2,3,1])
+/// ```
+pub trait ParallelSliceMut<'data,T> {
+    /// Mutable chunks of size elements, the last of which may be shorter, as with `slice::chunks_mut`
+    fn par_chunks_mut(&'data mut self, size:usize) -> ParallelChunksMut<'data,T>;
+}
+
+impl<'data,T> ParallelSliceMut<'data,T> for [T]
+where T: 'data
+{
+    fn par_chunks_mut(&'data mut self, size:usize) -> ParallelChunksMut<'data,T> {
+        let chunks = self.chunks_mut(size);
+        let len = chunks.len();
+        ParallelIterator::new(SizedQueue::new(chunks, len))
+    }
+}
@@ -1,6 +1,7 @@
-//! Structure to allow direct and by reference fetching of values from Vectors.
+//! Structure to allow direct, by reference and by mutable reference fetching of values from Vectors and slices.
 
-use crate::iterators::prelude::DiscreteQueue;
+use std::collections::VecDeque;
+use crate::iterators::prelude::{DiscreteQueue, ExactSizeQueue, IndexedQueue};
 
 // Initial workers are part of task scheduling algorithm used to decide number of initial threads that are launched.
 const QUEUE_SPLIT:usize = crate::push_workers::worker_controller::INITIAL_WORKERS;
@@ -49,19 +50,49 @@ impl<T> DiscreteQueue for FetchDirect<T> {
     }
 }
 
+impl<T> ExactSizeQueue for FetchDirect<T> {
+    fn exact_len(&self) -> usize {
+        self.vec.len()
+    }
+}
+
+/// The values left out are dropped in place, so the Vec is never reallocated
+impl<T> IndexedQueue for FetchDirect<T> {
+    fn narrow_take(&mut self, n:usize) {
+        self.vec.truncate(n);
+    }
+
+    fn narrow_skip(&mut self, n:usize) {
+        self.vec.drain(..usize::min(n, self.vec.len()));
+    }
+
+    fn narrow_step_by(&mut self, step:usize) {
+        let mut pos = 0;
+        self.vec.retain(|_| {
+            pos += 1;
+            (pos - 1) % step == 0
+        });
+    }
+}
+
+/// FetchInDirect hands out references to the values of a slice, which may be borrowed from a Vec, an array
+/// or any other contiguous buffer.
 pub struct FetchInDirect<'data, T> {
-    vec: &'data Vec<T>,    
+    vec: &'data [T],    
     start:usize,    
     queue_size:usize,    
+    // Distance between the positions of the values given, which is 1 unless narrowed by step_by
+    step:usize
 }
 
 impl<'data, T> FetchInDirect<'data, T> {
-    pub fn new(vec: &'data Vec<T>) -> Self {        
+    pub fn new(vec: &'data [T]) -> Self {        
         let optimal_q_size = vec.len() / QUEUE_SPLIT; 
         Self {
             vec,
             start:0,                        
             queue_size: optimal_q_size,            
+            step:1
         }
     }
 
@@ -75,27 +106,162 @@ impl<'data, T> DiscreteQueue for FetchInDirect<'data, T> {
 
     fn pop(&mut self) -> Option<Self::Output> {
         let start = self.start;
-        self.start += 1;
+        self.start = usize::min(self.start.saturating_add(self.step), self.vec.len());
         self.vec.get(start)                                
     }
 
     fn pull(&mut self) -> Option<Vec<Self::Output>> {
-        let size = usize::min(self.vec.len()-self.start,self.queue_size);
+        let size = usize::min(self.exact_len(),self.queue_size);
         if size == 0 {
             None
         } else {
             let start = self.start;
-            let end = start+ size;
-            self.start += size;
-            Some(self.vec[start..end].iter().collect::<Vec<Self::Output>>())                
+            let end = start + (size - 1) * self.step + 1;
+            self.start = usize::min((end - 1).saturating_add(self.step), self.vec.len());
+            Some(self.vec[start..end].iter().step_by(self.step).collect::<Vec<Self::Output>>())                
         }       
     }
 
     fn is_active(&self) -> bool {
-       !self.vec.is_empty()
+       self.start < self.vec.len()
+    }
+
+    fn len(&self) -> Option<usize> {
+        Some(self.exact_len())
+    }
+}
+
+impl<'data, T> ExactSizeQueue for FetchInDirect<'data, T> {
+    fn exact_len(&self) -> usize {
+        (self.vec.len() - self.start).div_ceil(self.step)
+    }
+}
+
+impl<'data, T> IndexedQueue for FetchInDirect<'data, T> {
+    fn narrow_take(&mut self, n:usize) {
+        let end = self.start.saturating_add(n.saturating_mul(self.step));
+        self.vec = &self.vec[..usize::min(end, self.vec.len())];
+    }
+
+    fn narrow_skip(&mut self, n:usize) {
+        let start = self.start.saturating_add(n.saturating_mul(self.step));
+        self.start = usize::min(start, self.vec.len());
+    }
+
+    fn narrow_step_by(&mut self, step:usize) {
+        self.step = self.step.saturating_mul(step);
+    }
+}
+/// FetchInDirectMut hands out mutable references to the values of a slice. The slice is split into disjoint parts as
+/// the values are pulled, so that each reference is given to exactly one thread.
+pub struct FetchInDirectMut<'data, T> {
+    slice: &'data mut [T],
+    queue_size:usize,
+}
+
+impl<'data, T> FetchInDirectMut<'data, T> {
+    pub fn new(slice: &'data mut [T]) -> Self {
+        let optimal_q_size = slice.len() / QUEUE_SPLIT;
+        Self {
+            slice,
+            queue_size: optimal_q_size,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.slice.len()
+    }
+}
+
+impl<'data, T> DiscreteQueue for FetchInDirectMut<'data, T> {
+    type Output = &'data mut T;
+
+    fn pop(&mut self) -> Option<Self::Output> {
+        let (first, rest) = std::mem::take(&mut self.slice).split_first_mut()?;
+        self.slice = rest;
+        Some(first)
+    }
+
+    fn pull(&mut self) -> Option<Vec<Self::Output>> {
+        let size = usize::min(self.slice.len(),self.queue_size);
+        if size == 0 {
+            None
+        } else {
+            let (head, tail) = std::mem::take(&mut self.slice).split_at_mut(size);
+            self.slice = tail;
+            Some(head.iter_mut().collect::<Vec<Self::Output>>())
+        }
+    }
+
+    fn is_active(&self) -> bool {
+       !self.slice.is_empty()
     }
 
     fn len(&self) -> Option<usize> {
         Some(self.len())
     }
-}
\ No newline at end of file
+}
+
+impl<'data, T> ExactSizeQueue for FetchInDirectMut<'data, T> {
+    fn exact_len(&self) -> usize {
+        self.slice.len()
+    }
+}
+
+/// FetchInDirectDeque hands out references to the values of a VecDeque, pulling them by their index as the
+/// values may be split across the two ends of its buffer.
+pub struct FetchInDirectDeque<'data, T> {
+    deque: &'data VecDeque<T>,
+    start:usize,
+    queue_size:usize,
+}
+
+impl<'data, T> FetchInDirectDeque<'data, T> {
+    pub fn new(deque: &'data VecDeque<T>) -> Self {
+        let optimal_q_size = deque.len() / QUEUE_SPLIT;
+        Self {
+            deque,
+            start:0,
+            queue_size: optimal_q_size,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.deque.len()
+    }
+}
+
+impl<'data, T> DiscreteQueue for FetchInDirectDeque<'data, T> {
+    type Output = &'data T;
+
+    fn pop(&mut self) -> Option<Self::Output> {
+        let start = self.start;
+        self.start = usize::min(self.start + 1, self.deque.len());
+        self.deque.get(start)
+    }
+
+    fn pull(&mut self) -> Option<Vec<Self::Output>> {
+        let size = usize::min(self.exact_len(),self.queue_size);
+        if size == 0 {
+            None
+        } else {
+            let start = self.start;
+            self.start += size;
+            Some(self.deque.range(start..start + size).collect::<Vec<Self::Output>>())
+        }
+    }
+
+    fn is_active(&self) -> bool {
+       self.start < self.deque.len()
+    }
+
+    fn len(&self) -> Option<usize> {
+        Some(self.exact_len())
+    }
+}
+
+impl<'data, T> ExactSizeQueue for FetchInDirectDeque<'data, T> {
+    fn exact_len(&self) -> usize {
+        self.deque.len().saturating_sub(self.start)
+    }
+}
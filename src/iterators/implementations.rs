@@ -1,8 +1,11 @@
 //! Implementations capture the implementation of ParallelIter and IntoParallelIter
-//! for commonly used collections like Vector, HashMap, Range and other relevant types
+//! for commonly used collections like Vector, slices, arrays, HashMap, Range and other relevant types
 
-use std::collections::{hash_map, HashMap};
-use crate::iterators::fetchdirect::{FetchDirect, FetchInDirect};
+use std::borrow::Cow;
+use std::collections::{btree_map, btree_set, hash_map, hash_set, linked_list, BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+use std::sync::Arc;
+use crate::iterators::fetchdirect::{FetchDirect, FetchInDirect, FetchInDirectDeque, FetchInDirectMut};
+use crate::iterators::ranges::RangeQueue;
 
 use super::{
     iterator::*,
@@ -32,6 +35,106 @@ where Self: 'data
     }       
 }
 
+/// Implementation for all slices
+impl<'data, T> ParallelIter<'data,FetchInDirect<'data, T>, T> for [T]
+where Self: 'data
+{
+    type RefItem = &'data T;
+    fn parallel_iter(&'data self) -> ParallelIterator<FetchInDirect<'data,T>, Self::RefItem>
+    {
+        ParallelIterator::new(FetchInDirect::new(self))
+     }
+}
+
+/// Implementation for all arrays
+impl<'data, T, const N:usize> ParallelIter<'data,FetchInDirect<'data, T>, T> for [T;N]
+where Self: 'data
+{
+    type RefItem = &'data T;
+    fn parallel_iter(&'data self) -> ParallelIterator<FetchInDirect<'data,T>, Self::RefItem>
+    {
+        ParallelIterator::new(FetchInDirect::new(self))
+     }
+}
+
+/// Implementation for all boxed slices
+impl<'data, T> ParallelIter<'data,FetchInDirect<'data, T>, T> for Box<[T]>
+where Self: 'data
+{
+    type RefItem = &'data T;
+    fn parallel_iter(&'data self) -> ParallelIterator<FetchInDirect<'data,T>, Self::RefItem>
+    {
+        ParallelIterator::new(FetchInDirect::new(self))
+     }
+}
+
+/// Implementation for all shared slices
+impl<'data, T> ParallelIter<'data,FetchInDirect<'data, T>, T> for Arc<[T]>
+where Self: 'data
+{
+    type RefItem = &'data T;
+    fn parallel_iter(&'data self) -> ParallelIterator<FetchInDirect<'data,T>, Self::RefItem>
+    {
+        ParallelIterator::new(FetchInDirect::new(self))
+     }
+}
+
+/// Implementation for all borrowed or owned slices
+impl<'data, 'a, T> ParallelIter<'data,FetchInDirect<'data, T>, T> for Cow<'a,[T]>
+where Self: 'data,
+T: Clone
+{
+    type RefItem = &'data T;
+    fn parallel_iter(&'data self) -> ParallelIterator<FetchInDirect<'data,T>, Self::RefItem>
+    {
+        ParallelIterator::new(FetchInDirect::new(self))
+     }
+}
+
+/// Implementation for all arrays
+impl<'data, T, const N:usize> IntoParallelIter<'data, FetchDirect<T>, T> for [T;N]
+where Self: 'data
+{
+    type IntoItem = T;
+
+    fn into_parallel_iter(self) -> ParallelIterator<FetchDirect<T>, Self::IntoItem> {
+        ParallelIterator::new(FetchDirect::new(Vec::from(self)))
+    }
+}
+
+/// Implementation for all boxed slices
+impl<'data, T> IntoParallelIter<'data, FetchDirect<T>, T> for Box<[T]>
+where Self: 'data
+{
+    type IntoItem = T;
+
+    fn into_parallel_iter(self) -> ParallelIterator<FetchDirect<T>, Self::IntoItem> {
+        ParallelIterator::new(FetchDirect::new(self.into_vec()))
+    }
+}
+
+/// Implementation for all Vectors
+impl<'data, T> ParallelIterMut<'data,FetchInDirectMut<'data, T>, T> for Vec<T>
+where Self: 'data
+{
+    type MutItem = &'data mut T;
+    fn parallel_iter_mut(&'data mut self) -> ParallelIterator<FetchInDirectMut<'data,T>, Self::MutItem>
+    {
+        ParallelIterator::new(FetchInDirectMut::new(self))
+     }
+}
+
+/// Implementation for all slices
+impl<'data, T> ParallelIterMut<'data,FetchInDirectMut<'data, T>, T> for [T]
+where Self: 'data
+{
+    type MutItem = &'data mut T;
+    fn parallel_iter_mut(&'data mut self) -> ParallelIterator<FetchInDirectMut<'data,T>, Self::MutItem>
+    {
+        ParallelIterator::new(FetchInDirectMut::new(self))
+     }
+}
+
 /// Implementation for all HashMap
 impl<'data, K,V> ParallelIter<'data, SizedQueue<hash_map::Iter<'data, K, V>, (&'data K, &'data V)>,(&'data K, &'data V)> for HashMap<K,V>
 where Self: 'data
@@ -45,6 +148,19 @@ where Self: 'data
      }          
 }
 
+/// Implementation for all HashMap. The keys are shared while the values may be updated.
+impl<'data, K,V> ParallelIterMut<'data, SizedQueue<hash_map::IterMut<'data, K, V>, (&'data K, &'data mut V)>,(&'data K, &'data mut V)> for HashMap<K,V>
+where Self: 'data
+{
+    type MutItem = (&'data K, &'data mut V);
+    fn parallel_iter_mut(&'data mut self) -> ParallelIterator<SizedQueue<hash_map::IterMut<'data, K, V>, (&'data K, &'data mut V)>, Self::MutItem>
+    {
+        let len = self.len();
+        let q: hash_map::IterMut<'_, K, V> = self.iter_mut();
+        ParallelIterator::new(SizedQueue::new(q, len))
+     }
+}
+
 impl<'data, K,V> IntoParallelIter<'data,SizedQueue<hash_map::IntoIter<K, V> ,(K, V)>,(K,V)> for HashMap<K,V>
 where Self: 'data
 {
@@ -57,23 +173,160 @@ where Self: 'data
     }       
 }
 
-// Implementation for Range - usize isize i32 i64 u32 u64
+/// Implementation for all VecDeque. The values are pulled by their index.
+impl<'data, T> ParallelIter<'data,FetchInDirectDeque<'data, T>, T> for VecDeque<T>
+where Self: 'data
+{
+    type RefItem = &'data T;
+    fn parallel_iter(&'data self) -> ParallelIterator<FetchInDirectDeque<'data,T>, Self::RefItem>
+    {
+        ParallelIterator::new(FetchInDirectDeque::new(self))
+     }
+}
+
+/// Implementation for all VecDeque. The values are first made contiguous so that the buffer may be split.
+impl<'data, T> ParallelIterMut<'data,FetchInDirectMut<'data, T>, T> for VecDeque<T>
+where Self: 'data
+{
+    type MutItem = &'data mut T;
+    fn parallel_iter_mut(&'data mut self) -> ParallelIterator<FetchInDirectMut<'data,T>, Self::MutItem>
+    {
+        ParallelIterator::new(FetchInDirectMut::new(self.make_contiguous()))
+     }
+}
+
+/// Implementation for all VecDeque. The buffer is reused by the Vec the values are pulled from.
+impl<'data, T> IntoParallelIter<'data, FetchDirect<T>, T> for VecDeque<T>
+where Self: 'data
+{
+    type IntoItem = T;
+
+    fn into_parallel_iter(self) -> ParallelIterator<FetchDirect<T>, Self::IntoItem> {
+        ParallelIterator::new(FetchDirect::new(Vec::from(self)))
+    }
+}
+
+/// Implementation for all BinaryHeap. The values are given in an arbitrary order, as with .iter()
+impl<'data, T> ParallelIter<'data,FetchInDirect<'data, T>, T> for BinaryHeap<T>
+where Self: 'data
+{
+    type RefItem = &'data T;
+    fn parallel_iter(&'data self) -> ParallelIterator<FetchInDirect<'data,T>, Self::RefItem>
+    {
+        ParallelIterator::new(FetchInDirect::new(self.as_slice()))
+     }
+}
+
+/// Implementation for all BinaryHeap. The values are given in an arbitrary order, as with .into_iter()
+impl<'data, T> IntoParallelIter<'data, FetchDirect<T>, T> for BinaryHeap<T>
+where Self: 'data
+{
+    type IntoItem = T;
+
+    fn into_parallel_iter(self) -> ParallelIterator<FetchDirect<T>, Self::IntoItem> {
+        ParallelIterator::new(FetchDirect::new(self.into_vec()))
+    }
+}
+
+// Implementation for the collections that are pulled from in sequence - HashSet BTreeSet BTreeMap LinkedList. The values
+// are pulled in bounded batches, so that the threads start before the whole collection has been stepped through.
+macro_rules! batch_queue_impl {
+    {$($C:ident<$($P:ident),*> => $Iter:ty, $RefItem:ty; $IntoIter:ty, $IntoItem:ty;)*} => {
+        $(
+            impl<'data, $($P),*> ParallelIter<'data, BatchQueue<$Iter>, $RefItem> for $C<$($P),*>
+            where Self: 'data
+            {
+                type RefItem = $RefItem;
+                fn parallel_iter(&'data self) -> ParallelIterator<BatchQueue<$Iter>, Self::RefItem>
+                {
+                    let len = self.len();
+                    ParallelIterator::new(BatchQueue::new(self.iter(), len))
+                }
+            }
+
+            impl<'data, $($P),*> IntoParallelIter<'data, BatchQueue<$IntoIter>, $IntoItem> for $C<$($P),*>
+            where Self: 'data
+            {
+                type IntoItem = $IntoItem;
+
+                fn into_parallel_iter(self) -> ParallelIterator<BatchQueue<$IntoIter>, Self::IntoItem> {
+                    let len = self.len();
+                    ParallelIterator::new(BatchQueue::new(self.into_iter(), len))
+                }
+            }
+        )*
+    };
+}
+
+batch_queue_impl! {
+    HashSet<T> => hash_set::Iter<'data, T>, &'data T; hash_set::IntoIter<T>, T;
+    BTreeSet<T> => btree_set::Iter<'data, T>, &'data T; btree_set::IntoIter<T>, T;
+    BTreeMap<K,V> => btree_map::Iter<'data, K, V>, (&'data K, &'data V); btree_map::IntoIter<K, V>, (K, V);
+    LinkedList<T> => linked_list::Iter<'data, T>, &'data T; linked_list::IntoIter<T>, T;
+}
+
+/// Implementation for all BTreeMap. The keys are shared while the values may be updated.
+impl<'data, K,V> ParallelIterMut<'data, BatchQueue<btree_map::IterMut<'data, K, V>>,(&'data K, &'data mut V)> for BTreeMap<K,V>
+where Self: 'data
+{
+    type MutItem = (&'data K, &'data mut V);
+    fn parallel_iter_mut(&'data mut self) -> ParallelIterator<BatchQueue<btree_map::IterMut<'data, K, V>>, Self::MutItem>
+    {
+        let len = self.len();
+        ParallelIterator::new(BatchQueue::new(self.iter_mut(), len))
+     }
+}
+
+/// Implementation for all LinkedList
+impl<'data, T> ParallelIterMut<'data, BatchQueue<linked_list::IterMut<'data, T>>, &'data mut T> for LinkedList<T>
+where Self: 'data
+{
+    type MutItem = &'data mut T;
+    fn parallel_iter_mut(&'data mut self) -> ParallelIterator<BatchQueue<linked_list::IterMut<'data, T>>, Self::MutItem>
+    {
+        let len = self.len();
+        ParallelIterator::new(BatchQueue::new(self.iter_mut(), len))
+     }
+}
+
+// Implementation for Range and RangeInclusive over the integers and char
 macro_rules! range_impl {
     {$($T:ty)*} => {
         $(
-            impl<'data> IntoParallelIter<'data,SizedQueue<std::ops::Range<$T>,$T>,$T> for std::ops::Range<$T>
+            impl<'data> IntoParallelIter<'data,RangeQueue<$T>,$T> for std::ops::Range<$T>
             where Self: 'data
             {
                 type IntoItem = $T;                
                 
-                fn into_parallel_iter(self) -> ParallelIterator<SizedQueue<std::ops::Range<$T>,$T>, Self::IntoItem> {                    
-                    let len = self.end - self.start;                                                                             
-                    let x = ParallelIterator::new(SizedQueue::new(self,len as usize));                    
-                    x
+                /// # Panics
+                ///
+                /// Panics if the range holds more than `usize::MAX` values. `RangeQueue::try_exclusive` checks for
+                /// this instead.
+                fn into_parallel_iter(self) -> ParallelIterator<RangeQueue<$T>, Self::IntoItem> {                    
+                    ParallelIterator::new(RangeQueue::exclusive(self.start, self.end))
                 }       
             }
+
+            impl<'data> IntoParallelIter<'data,RangeQueue<$T>,$T> for std::ops::RangeInclusive<$T>
+            where Self: 'data
+            {
+                type IntoItem = $T;
+
+                /// # Panics
+                ///
+                /// Panics if the range holds more than `usize::MAX` values, as `0..=usize::MAX` does.
+                /// `RangeQueue::try_inclusive` checks for this instead.
+                fn into_parallel_iter(self) -> ParallelIterator<RangeQueue<$T>, Self::IntoItem> {
+                    if self.is_empty() {
+                        // An exhausted RangeInclusive may still hold start <= end
+                        ParallelIterator::new(RangeQueue::new(*self.start(), 0))
+                    } else {
+                        ParallelIterator::new(RangeQueue::inclusive(*self.start(), *self.end()))
+                    }
+                }
+            }
         )*
     };
 }
 
-range_impl! { usize isize i32 i64 u32 u64 }
+range_impl! { u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize char }
@@ -6,11 +6,10 @@
 use std::marker::PhantomData;
 
 /// ParallelIter gives a version of ParallelIterator that is expected to capture the .iter output
-/// for those that implement the same like Vec, HashMap and so on. 
+/// for those that implement the same like Vec, slices, HashMap and so on. 
 #[allow(dead_code)]
 pub trait ParallelIter<'data,DiscQ, T>
-where Self:Sized,
-DiscQ: DiscreteQueue<Output=Self::RefItem>
+where DiscQ: DiscreteQueue<Output=Self::RefItem>
 {    
     type RefItem; 
     fn parallel_iter(&'data self) -> ParallelIterator<DiscQ, Self::RefItem>;    
@@ -27,6 +26,23 @@ DiscQ: DiscreteQueue<Output=Self::IntoItem>
     fn into_parallel_iter(self) -> ParallelIterator<DiscQ,Self::IntoItem>; 
 }
 
+/// ParallelIterMut gives a version of ParallelIterator that is expected to capture the .iter_mut output
+/// for those that implement the same like Vec, slices and HashMap. Each mutable reference is handed to exactly one thread.
+/// ```
+/// use parallel_task::prelude::*;
+///
+/// let mut vec = (0..100_000).collect::<Vec<i32>>();
+/// vec.parallel_iter_mut().for_each(|val| *val += 1);
+/// assert_eq!(vec,(1..100_001).collect::<Vec<i32>>())
+/// ```
+#[allow(dead_code)]
+pub trait ParallelIterMut<'data,DiscQ,T>
+where DiscQ: DiscreteQueue<Output=Self::MutItem>
+{
+    type MutItem;
+    fn parallel_iter_mut(&'data mut self) -> ParallelIterator<DiscQ, Self::MutItem>;
+}
+
 #[allow(clippy::len_without_is_empty)]
 pub trait DiscreteQueue 
 {
@@ -35,6 +51,31 @@ pub trait DiscreteQueue
     fn pull(&mut self) -> Option<Vec<Self::Output>>;
     fn is_active(&self) -> bool;
     fn len(&self) -> Option<usize>;
+    /// Tests whether the values are given over several pulls. By default this is the case for queues without a known
+    /// length.
+    fn is_pending(&self) -> bool {
+        self.len().is_none()
+    }
+}
+
+/// ExactSizeQueue is implemented by the queues that know how many values they hold before they are pulled from, such
+/// as those over Vec, slices, ranges and HashMaps.
+pub trait ExactSizeQueue: DiscreteQueue {
+    /// Number of values yet to be pulled from the queue
+    fn exact_len(&self) -> usize;
+}
+
+/// IndexedQueue is implemented by the queues whose values are found by their position, such as those over Vec,
+/// slices and ranges. They may be narrowed to a part of their values in place, without pulling the values left out.
+pub trait IndexedQueue: ExactSizeQueue {
+    /// Keeps only the first n values left in the queue
+    fn narrow_take(&mut self, n:usize);
+
+    /// Leaves out the first n values left in the queue
+    fn narrow_skip(&mut self, n:usize);
+
+    /// Keeps the first value left in the queue and every step-th value after it. step is never 0.
+    fn narrow_step_by(&mut self, step:usize);
 }
 
 /// ParallelIterator is comparable to Iter, but is set up for the AtomicIterator.
@@ -56,6 +97,38 @@ where DiscQ: DiscreteQueue<Output=T>,
     }
 }
 
+/// take, skip and step_by on the sources over Vec, slices and ranges narrow the queue of the source in place, so
+/// that the values left out are never pulled. Other sources are wrapped by the adapters of ParallelIndexedIter.
+/// ```
+/// use parallel_task::prelude::*;
+///
+/// // Only the ten values taken are ever computed
+/// let res = (0..u64::MAX).into_parallel_iter().skip(5).step_by(1 << 60).take(10).map(|val| val).collect::<Vec<u64>>();
+/// assert_eq!(res,(0..u64::MAX).skip(5).step_by(1 << 60).take(10).collect::<Vec<u64>>());
+/// ```
+impl<DiscQ,T> ParallelIterator<DiscQ,T> 
+where DiscQ: IndexedQueue<Output=T>,
+{
+    /// Gives only the first n values
+    pub fn take(mut self, n:usize) -> Self {
+        self.iter.narrow_take(n);
+        self
+    }
+
+    /// Leaves out the first n values
+    pub fn skip(mut self, n:usize) -> Self {
+        self.iter.narrow_skip(n);
+        self
+    }
+
+    /// Gives the first value and every step-th value after it. Panics if step is 0, as with `Iterator::step_by`.
+    pub fn step_by(mut self, step:usize) -> Self {
+        assert!(step != 0, "step must not be zero");
+        self.iter.narrow_step_by(step);
+        self
+    }
+}
+
 
 /// AtomicIterator trait is a special kind of iterator trait suited to enable both next and other
 /// larger data pulls as demanded by the parallelism algorithm and logic 
@@ -68,6 +141,11 @@ pub trait AtomicIterator {
     ///tests whether the iterator is still active with values still available
     /// to be pulled
     fn is_active(&self) -> bool;
+    /// Tests whether the values are given over several pulls, so that the source is pulled from again after the
+    /// first pull. Sources without a known length are pulled from in batches until they are exhausted.
+    fn is_pending(&self) -> bool {
+        self.len().is_none()
+    }
 }
 
 impl<DiscQ,T> AtomicIterator for ParallelIterator<DiscQ,T> 
@@ -90,4 +168,22 @@ where DiscQ:DiscreteQueue<Output = T>,
     fn atomic_pull(&mut self) -> Option<Vec<Self::AtomicItem>> {
         self.iter.pull()
     }
-}
\ No newline at end of file
+
+    fn is_pending(&self) -> bool {
+        self.iter.is_pending()
+    }
+}
+/// ExactSizeAtomicIterator is implemented by the sources whose length is known before they are pulled from. It is
+/// needed wherever the length must be known up front, as when collecting into a Vec or slice.
+pub trait ExactSizeAtomicIterator: AtomicIterator {
+    /// Number of values yet to be pulled from the source
+    fn exact_len(&self) -> usize;
+}
+
+impl<DiscQ,T> ExactSizeAtomicIterator for ParallelIterator<DiscQ,T>
+where DiscQ:ExactSizeQueue<Output = T>,
+{
+    fn exact_len(&self) -> usize {
+        self.iter.exact_len()
+    }
+}
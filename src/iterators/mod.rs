@@ -2,4 +2,9 @@ pub mod iterator;
 pub mod prelude;
 pub mod queued;
 pub mod implementations;
-pub(super) mod fetchdirect;
\ No newline at end of file
+pub mod bridge;
+pub mod reader;
+pub mod strings;
+pub mod chunks;
+pub(super) mod fetchdirect;
+pub mod ranges;
\ No newline at end of file
@@ -1,6 +1,8 @@
-use crate::iterators::prelude::DiscreteQueue;
+use crate::iterators::prelude::{DiscreteQueue, ExactSizeQueue};
 
 const QUEUE_SIZE:usize = crate::push_workers::worker_controller::INITIAL_WORKERS;
+const MIN_BATCH:usize = 16;
+const MAX_BATCH:usize = 1024;
 
 #[allow(dead_code)]
 pub struct SizedQueue<I,T> 
@@ -8,7 +10,9 @@ where I: Iterator<Item = T>
 {   
     queue: I,
     pull_size:usize,
-    len:usize    
+    len:usize,
+    // Values yet to be pulled, going by the length given
+    remaining:usize
 }
 
 /// SizedQueue allows atomic and parallel iterator to be built over HashMaps and Ranges.
@@ -25,7 +29,8 @@ where I: Iterator<Item = T>
         Self {
             queue,
             pull_size,
-            len               
+            len,
+            remaining: len
         }       
     }
     
@@ -41,7 +46,9 @@ where I: Iterator<Item = T>
     }
 
     fn pop(&mut self) -> Option<Self::Output> {                
-        self.queue.next()
+        let val = self.queue.next();
+        self.remaining = self.remaining.saturating_sub(usize::from(val.is_some()));
+        val
     }
     
     fn pull(&mut self) -> Option<Vec<Self::Output>> {
@@ -51,6 +58,7 @@ where I: Iterator<Item = T>
             if val.is_none() { break; }
             res.push(val.unwrap());
         }
+        self.remaining = self.remaining.saturating_sub(res.len());
         if res.is_empty() { None } else {
             Some(res)
         }        
@@ -60,4 +68,79 @@ where I: Iterator<Item = T>
         Some(self.len)
     }
 
-}
\ No newline at end of file
+}
+
+impl<I,T> ExactSizeQueue for SizedQueue<I,T> 
+where I: Iterator<Item = T>
+{
+    fn exact_len(&self) -> usize {
+        self.remaining
+    }
+}
+
+/// BatchQueue allows a collection that may only be stepped through in sequence, such as a BTreeMap or a LinkedList, to
+/// be a parallel source. The values are pulled in batches that grow up to a bound, rather than all at once, so that the
+/// threads start on the first batch while the WorkerController keeps pulling batches for the free threads until the
+/// collection is exhausted.
+pub struct BatchQueue<I>
+where I: Iterator
+{
+    queue: I,
+    batch:usize,
+    // Values yet to be pulled, going by the length given
+    remaining:usize
+}
+
+impl<I> BatchQueue<I>
+where I: Iterator
+{
+    pub fn new(queue:I, len:usize) -> Self {
+        Self {
+            queue,
+            batch: MIN_BATCH,
+            remaining: len
+        }
+    }
+}
+
+impl<I> DiscreteQueue for BatchQueue<I>
+where I: Iterator
+{
+    type Output = I::Item;
+
+    fn pop(&mut self) -> Option<Self::Output> {
+        let val = if self.remaining > 0 { self.queue.next() } else { None };
+        // The iterator is not called again once it has given None
+        self.remaining = if val.is_some() { self.remaining - 1 } else { 0 };
+        val
+    }
+
+    fn pull(&mut self) -> Option<Vec<Self::Output>> {
+        let size = usize::min(self.remaining, self.batch);
+        let res = self.queue.by_ref().take(size).collect::<Vec<Self::Output>>();
+        self.remaining = if res.len() < size { 0 } else { self.remaining - size };
+        // Batches double in size so that large collections need fewer pulls
+        self.batch = usize::min(self.batch * 2, MAX_BATCH);
+        if res.is_empty() { None } else { Some(res) }
+    }
+
+    fn is_active(&self) -> bool {
+        self.remaining > 0
+    }
+
+    fn len(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+
+    fn is_pending(&self) -> bool {
+        true
+    }
+}
+
+impl<I> ExactSizeQueue for BatchQueue<I>
+where I: Iterator
+{
+    fn exact_len(&self) -> usize {
+        self.remaining
+    }
+}
@@ -0,0 +1,216 @@
+//! Structure to allow Range and RangeInclusive to be split by index. Each value is computed from its offset to the start
+//! of the range, so the values are pulled without stepping through the range one at a time. Ranges may be far longer
+//! than would fit in memory, so the values are pulled in batches that grow up to a bound, and the WorkerController
+//! keeps pulling batches for the free threads until the range is exhausted.
+
+use crate::iterators::prelude::{DiscreteQueue, ExactSizeQueue, IndexedQueue};
+
+const MIN_BATCH:usize = 1024;
+const MAX_BATCH:usize = 1 << 16;
+
+/// RangeValue is implemented for the types a range may be iterated in parallel over.
+pub trait RangeValue: Copy {
+    /// Number of values from start up to but excluding end, where start is less than end. None if the number does
+    /// not fit in a usize.
+    fn distance(start:Self, end:Self) -> Option<usize>;
+
+    /// Value that is n steps after start
+    fn offset(start:Self, n:usize) -> Self;
+}
+
+// Implementation for the integers along with the unsigned type of the same width, on which the arithmetic is carried out
+macro_rules! range_value_impl {
+    {$($T:ty => $U:ty)*} => {
+        $(
+            impl RangeValue for $T {
+                fn distance(start:Self, end:Self) -> Option<usize> {
+                    let distance = (end as $U).wrapping_sub(start as $U);
+                    usize::try_from(distance).ok()
+                }
+
+                fn offset(start:Self, n:usize) -> Self {
+                    (start as $U).wrapping_add(n as $U) as $T
+                }
+            }
+        )*
+    };
+}
+
+range_value_impl! { u8 => u8 u16 => u16 u32 => u32 u64 => u64 u128 => u128 usize => usize i8 => u8 i16 => u16 i32 => u32 i64 => u64 i128 => u128 isize => usize }
+
+const SURROGATE_START:u32 = 0xD800;
+const SURROGATE_LEN:u32 = 0x800;
+
+/// Implementation for char which skips the surrogate code points, as char ranges do
+impl RangeValue for char {
+    fn distance(start:Self, end:Self) -> Option<usize> {
+        let (start, end) = (start as u32, end as u32);
+        let gap = if start < SURROGATE_START && end > SURROGATE_START { SURROGATE_LEN } else { 0 };
+        usize::try_from(end - start - gap).ok()
+    }
+
+    fn offset(start:Self, n:usize) -> Self {
+        let start = start as u32;
+        let mut value = start + n as u32;
+        if start < SURROGATE_START && value >= SURROGATE_START {
+            value += SURROGATE_LEN;
+        }
+        char::from_u32(value).expect("offset is within the range")
+    }
+}
+
+pub struct RangeQueue<T>
+where T: RangeValue
+{
+    start:T,
+    len:usize,
+    taken:usize,
+    batch:usize,
+    // Steps between the values given, which is 1 unless narrowed by step_by
+    step:usize
+}
+
+impl<T> RangeQueue<T>
+where T: RangeValue
+{
+    pub fn new(start:T, len:usize) -> Self {
+        Self {
+            start,
+            len,
+            taken:0,
+            batch:MIN_BATCH,
+            step:1
+        }
+    }
+
+    /// Queue over start..end, which is empty when end is not after start
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range holds more than `usize::MAX` values, as `start..end` over u128 or i128 may. Use
+    /// `try_exclusive` to check for this instead.
+    pub fn exclusive(start:T, end:T) -> Self
+    where T: PartialOrd
+    {
+        Self::try_exclusive(start, end).expect("range is too long to be iterated in parallel")
+    }
+
+    /// Queue over start..=end, which is empty when end is before start
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range holds more than `usize::MAX` values, as `0..=usize::MAX` or `0..=u64::MAX` on a 64-bit
+    /// target do. Use `try_inclusive` to check for this instead.
+    pub fn inclusive(start:T, end:T) -> Self
+    where T: PartialOrd
+    {
+        Self::try_inclusive(start, end).expect("range is too long to be iterated in parallel")
+    }
+
+    /// Queue over start..end, or None if the range holds more than `usize::MAX` values
+    /// ```
+    /// use parallel_task::prelude::*;
+    /// use parallel_task::iterators::{iterator::ParallelIterator, ranges::RangeQueue};
+    ///
+    /// assert!(RangeQueue::try_exclusive(0, u128::MAX).is_none());
+    /// let queue = RangeQueue::try_exclusive(u128::MAX - 10, u128::MAX).unwrap();
+    /// assert_eq!(ParallelIterator::new(queue).count(),10);
+    /// ```
+    pub fn try_exclusive(start:T, end:T) -> Option<Self>
+    where T: PartialOrd
+    {
+        let len = if start < end { T::distance(start, end)? } else { 0 };
+        Some(Self::new(start, len))
+    }
+
+    /// Queue over start..=end, or None if the range holds more than `usize::MAX` values
+    /// ```
+    /// use parallel_task::prelude::*;
+    /// use parallel_task::iterators::{iterator::ParallelIterator, ranges::RangeQueue};
+    ///
+    /// assert!(RangeQueue::try_inclusive(0, usize::MAX).is_none());
+    /// let queue = RangeQueue::try_inclusive(1, usize::MAX).unwrap();
+    /// assert_eq!(ParallelIterator::new(queue).skip(usize::MAX - 2).map(|val| val).collect::<Vec<usize>>(),vec![usize::MAX - 1, usize::MAX]);
+    /// ```
+    pub fn try_inclusive(start:T, end:T) -> Option<Self>
+    where T: PartialOrd
+    {
+        let len = if start <= end {
+            T::distance(start, end)?.checked_add(1)?
+        } else {
+            0
+        };
+        Some(Self::new(start, len))
+    }
+}
+
+impl<T> DiscreteQueue for RangeQueue<T>
+where T: RangeValue
+{
+    type Output = T;
+
+    fn pop(&mut self) -> Option<Self::Output> {
+        if self.taken < self.len {
+            let value = T::offset(self.start, self.taken * self.step);
+            self.taken += 1;
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    fn pull(&mut self) -> Option<Vec<Self::Output>> {
+        let size = usize::min(self.len - self.taken, self.batch);
+        if size == 0 {
+            None
+        } else {
+            let taken = self.taken;
+            self.taken += size;
+            // Batches double in size so that long ranges need fewer pulls
+            self.batch = usize::min(self.batch * 2, MAX_BATCH);
+            Some((taken..taken + size).map(|n| T::offset(self.start, n * self.step)).collect::<Vec<Self::Output>>())
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        self.taken < self.len
+    }
+
+    fn len(&self) -> Option<usize> {
+        Some(self.exact_len())
+    }
+
+    fn is_pending(&self) -> bool {
+        true
+    }
+}
+
+impl<T> ExactSizeQueue for RangeQueue<T>
+where T: RangeValue
+{
+    fn exact_len(&self) -> usize {
+        self.len - self.taken
+    }
+}
+
+impl<T> IndexedQueue for RangeQueue<T>
+where T: RangeValue
+{
+    fn narrow_take(&mut self, n:usize) {
+        self.len = usize::min(self.len, self.taken.saturating_add(n));
+    }
+
+    fn narrow_skip(&mut self, n:usize) {
+        self.taken = usize::min(self.len, self.taken.saturating_add(n));
+    }
+
+    /// The queue is restarted from the next value, so that the n-th value left is n steps after it
+    fn narrow_step_by(&mut self, step:usize) {
+        if self.taken < self.len {
+            self.start = T::offset(self.start, self.taken * self.step);
+        }
+        self.len = (self.len - self.taken).div_ceil(step);
+        self.taken = 0;
+        self.step = self.step.saturating_mul(step);
+    }
+}
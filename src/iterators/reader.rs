@@ -0,0 +1,354 @@
+//! ReadRegions allows a File, or any reader that implements Read and Seek, to be processed in parallel as lines or
+//! delimited records. The queue hands out byte regions of the reader rather than values already read, so that the reading
+//! and splitting of each region happens within the worker threads.
+//! A region owns the records that start within it. The worker reading a region skips the partial record at its start,
+//! which belongs to the previous region, and reads past its end to complete its last record.
+//! A File given to ReadRegions::from_file is read at the offset of each region without moving a shared cursor, and a
+//! reader opened by ReadRegions::from_opener is opened once by each worker thread, so that the workers read at the same
+//! time. Any other reader is shared behind a lock, so that one region is read at a time.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::sync::{Arc, Mutex};
+use crate::iterators::prelude::{DiscreteQueue, ParallelIterator};
+use crate::map::ParallelMap;
+use crate::pipeline::Pipeline;
+
+// Initial workers are part of task scheduling algorithm used to decide number of initial threads that are launched.
+const QUEUE_SPLIT:usize = crate::push_workers::worker_controller::INITIAL_WORKERS;
+const DEFAULT_REGION_SIZE:u64 = 1 << 20;
+
+/// Lines of a reader, run in parallel. Each line is given without the newline, as with `BufRead::lines`.
+pub type ParallelLines<R> = ParallelMap<Region<R>,Lines,io::Result<String>,ParallelIterator<ReadRegions<R>,Region<R>>>;
+
+/// Delimited records of a reader, run in parallel. Each record is given without the delimiter.
+pub type ParallelRecords<R> = ParallelMap<Region<R>,Records,io::Result<Vec<u8>>,ParallelIterator<ReadRegions<R>,Region<R>>>;
+
+type Opener<R> = Box<dyn Fn() -> io::Result<R> + Send + Sync>;
+
+/// Where the regions are read from
+enum Source<R> {
+    /// Reader shared by the workers, which read one region at a time
+    Shared(Mutex<R>),
+    /// Opens a reader for each worker thread
+    Opener(Opener<R>),
+    /// File read at the offset of each region, without a cursor
+    #[cfg(any(unix, windows))]
+    File(File)
+}
+
+/// Reads a File from pos onwards without moving its cursor, so that the File may be read by many threads at once
+#[cfg(any(unix, windows))]
+struct FileAt<'a> {
+    file:&'a File,
+    pos:u64
+}
+
+#[cfg(any(unix, windows))]
+impl Read for FileAt<'_> {
+    fn read(&mut self, buf:&mut [u8]) -> io::Result<usize> {
+        #[cfg(unix)]
+        let read = std::os::unix::fs::FileExt::read_at(self.file, buf, self.pos)?;
+        #[cfg(windows)]
+        let read = std::os::windows::fs::FileExt::seek_read(self.file, buf, self.pos)?;
+        self.pos += read as u64;
+        Ok(read)
+    }
+}
+
+/// Region of the reader from start up to but excluding end
+pub struct Region<R> {
+    source:Arc<Source<R>>,
+    start:u64,
+    end:u64,
+    delimiter:u8
+}
+
+impl<R> Region<R>
+where R: Read + Seek
+{
+    /// Reads the records starting within the region. A shared reader is only locked while the bytes are read, and a
+    /// reader given by an opener is opened for the region.
+    pub fn read_records(&self) -> io::Result<Vec<Vec<u8>>> {
+        self.read_records_with(&mut None)
+    }
+
+    /// Reads the records starting within the region. A reader given by an opener is kept in handle, so that it is
+    /// reused for the next region read by the same thread.
+    fn read_records_with(&self, handle:&mut Option<R>) -> io::Result<Vec<Vec<u8>>> {
+        let bytes = self.read_bytes(handle)?;
+        if bytes.is_empty() {
+            return Ok(Vec::new());
+        }
+        let body = bytes.strip_suffix(&[self.delimiter]).unwrap_or(&bytes);
+        Ok(body.split(|byte| *byte == self.delimiter).map(|record| record.to_vec()).collect())
+    }
+
+    fn read_bytes(&self, handle:&mut Option<R>) -> io::Result<Vec<u8>> {
+        // Start a byte early so that a record starting exactly at the region start is not skipped
+        let from = self.start.saturating_sub(1);
+        match &*self.source {
+            Source::Shared(reader) => {
+                let mut reader = reader.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+                reader.seek(SeekFrom::Start(from))?;
+                self.read_from(&mut *reader, from)
+            }
+            Source::Opener(open) => {
+                let reader = match handle {
+                    Some(reader) => reader,
+                    empty => empty.insert(open()?)
+                };
+                reader.seek(SeekFrom::Start(from))?;
+                self.read_from(reader, from)
+            }
+            #[cfg(any(unix, windows))]
+            Source::File(file) => self.read_from(FileAt { file, pos: from }, from)
+        }
+    }
+
+    /// Reads the region from a reader placed at from
+    fn read_from<B>(&self, reader:B, from:u64) -> io::Result<Vec<u8>>
+    where B: Read
+    {
+        let mut buf = BufReader::new(reader);
+        let mut skipped = Vec::new();
+        if self.start > 0 {
+            buf.read_until(self.delimiter, &mut skipped)?;
+        }
+        let first = from + skipped.len() as u64;
+        let mut bytes = Vec::new();
+        if first < self.end {
+            (&mut buf).take(self.end - first).read_to_end(&mut bytes)?;
+            if bytes.last().is_some_and(|byte| *byte != self.delimiter) {
+                buf.read_until(self.delimiter, &mut bytes)?;
+            }
+        }
+        Ok(bytes)
+    }
+}
+
+/// ReadRegions splits the reader into regions of about the same size. The records are split on newline unless
+/// another delimiter is given. A reader given to ReadRegions::new is shared behind a lock, so a File is better given to
+/// ReadRegions::from_file, and other readers that may be opened more than once to ReadRegions::from_opener.
+/// ```
+/// use std::io::Cursor;
+/// use parallel_task::prelude::*;
+/// use parallel_task::iterators::reader::ReadRegions;
+///
+/// let reader = Cursor::new("a,bb,ccc".as_bytes().to_vec());
+/// let res = ReadRegions::new(reader).unwrap()
+/// .with_delimiter(b',')
+/// .with_region_size(2)
+/// .records()
+/// .map(|record| record.unwrap().len())
+/// .collect::<Vec<usize>>();
+/// assert_eq!(res,vec![1,2,3])
+/// ```
+pub struct ReadRegions<R> {
+    source:Arc<Source<R>>,
+    len:u64,
+    next:u64,
+    region_size:u64,
+    delimiter:u8
+}
+
+impl<R> ReadRegions<R>
+where R: Read + Seek + Send
+{
+    /// Shares the reader between the worker threads, which read one region at a time
+    pub fn new(mut reader:R) -> io::Result<Self> {
+        let len = reader.seek(SeekFrom::End(0))?;
+        Ok(Self::with_source(Source::Shared(Mutex::new(reader)), len))
+    }
+
+    /// Calls open once for the length and then once on each worker thread, so that each worker reads through its own
+    /// reader.
+    /// ```
+    /// use std::io::Cursor;
+    /// use std::sync::Arc;
+    /// use parallel_task::prelude::*;
+    /// use parallel_task::iterators::reader::ReadRegions;
+    ///
+    /// let data:Arc<[u8]> = Arc::from("a\nbb\nccc".as_bytes());
+    /// let res = ReadRegions::from_opener(move || Ok(Cursor::new(data.clone()))).unwrap()
+    /// .with_region_size(2)
+    /// .lines()
+    /// .map(|line| line.unwrap().len())
+    /// .collect::<Vec<usize>>();
+    /// assert_eq!(res,vec![1,2,3])
+    /// ```
+    pub fn from_opener<F>(open:F) -> io::Result<Self>
+    where F: Fn() -> io::Result<R> + Send + Sync + 'static
+    {
+        let len = open()?.seek(SeekFrom::End(0))?;
+        Ok(Self::with_source(Source::Opener(Box::new(open)), len))
+    }
+
+    fn with_source(source:Source<R>, len:u64) -> Self {
+        Self {
+            source: Arc::new(source),
+            len,
+            next: 0,
+            region_size: DEFAULT_REGION_SIZE,
+            delimiter: b'\n'
+        }
+    }
+
+    /// Sets the byte on which the records are split
+    pub fn with_delimiter(mut self, delimiter:u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Sets the number of bytes in each region. Smaller regions allow the work to be spread across more threads.
+    pub fn with_region_size(mut self, region_size:u64) -> Self {
+        self.region_size = u64::max(region_size, 1);
+        self
+    }
+
+    /// Runs the lines of the reader in parallel. A trailing carriage return is removed along with the newline.
+    pub fn lines(self) -> ParallelLines<R> {
+        ParallelMap::new(ParallelIterator::new(self), Lines)
+    }
+
+    /// Runs the delimited records of the reader in parallel
+    pub fn records(self) -> ParallelRecords<R> {
+        ParallelMap::new(ParallelIterator::new(self), Records)
+    }
+
+    fn remaining(&self) -> usize {
+        usize::try_from((self.len - self.next).div_ceil(self.region_size)).unwrap_or(usize::MAX)
+    }
+}
+
+impl ReadRegions<File> {
+    /// Reads the regions of the File at their offsets, so that the worker threads read at the same time without a
+    /// lock. Where positioned reads are not supported, the File is shared behind a lock as with ReadRegions::new.
+    pub fn from_file(file:File) -> io::Result<Self> {
+        let len = file.metadata()?.len();
+        #[cfg(any(unix, windows))]
+        let source = Source::File(file);
+        #[cfg(not(any(unix, windows)))]
+        let source = Source::Shared(Mutex::new(file));
+        Ok(Self::with_source(source, len))
+    }
+}
+
+impl<R> DiscreteQueue for ReadRegions<R>
+where R: Read + Seek + Send
+{
+    type Output = Region<R>;
+
+    fn pop(&mut self) -> Option<Self::Output> {
+        if self.next < self.len {
+            let start = self.next;
+            self.next = u64::min(start + self.region_size, self.len);
+            Some(Region { source: self.source.clone(), start, end: self.next, delimiter: self.delimiter })
+        } else {
+            None
+        }
+    }
+
+    fn pull(&mut self) -> Option<Vec<Self::Output>> {
+        let size = self.remaining().div_ceil(QUEUE_SPLIT);
+        let res = (0..size).map_while(|_| self.pop()).collect::<Vec<Self::Output>>();
+        if res.is_empty() { None } else { Some(res) }
+    }
+
+    fn is_active(&self) -> bool {
+        self.next < self.len
+    }
+
+    fn len(&self) -> Option<usize> {
+        Some(self.remaining())
+    }
+}
+
+/// Lines reads the lines of a region within the worker thread
+pub struct Lines;
+
+impl<R> Pipeline<Region<R>> for Lines
+where R: Read + Seek + Send
+{
+    type Output = io::Result<String>;
+    // Reader of the thread, where the regions are read through an opener
+    type State = Option<R>;
+
+    fn init_state(&self) -> Self::State {
+        None
+    }
+
+    fn feed<S>(&self, handle:&mut Self::State, value:Region<R>, sink:&mut S) -> bool
+    where S: FnMut(Self::Output) -> bool {
+        match value.read_records_with(handle) {
+            Ok(records) => records.into_iter().all(|mut record| {
+                if record.last() == Some(&b'\r') {
+                    record.pop();
+                }
+                sink(String::from_utf8(record).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)))
+            }),
+            Err(e) => sink(Err(e))
+        }
+    }
+}
+
+/// Records reads the delimited records of a region within the worker thread
+pub struct Records;
+
+impl<R> Pipeline<Region<R>> for Records
+where R: Read + Seek + Send
+{
+    type Output = io::Result<Vec<u8>>;
+    // Reader of the thread, where the regions are read through an opener
+    type State = Option<R>;
+
+    fn init_state(&self) -> Self::State {
+        None
+    }
+
+    fn feed<S>(&self, handle:&mut Self::State, value:Region<R>, sink:&mut S) -> bool
+    where S: FnMut(Self::Output) -> bool {
+        match value.read_records_with(handle) {
+            Ok(records) => records.into_iter().all(|record| sink(Ok(record))),
+            Err(e) => sink(Err(e))
+        }
+    }
+}
+
+/// ParallelRead allows the lines or records of any reader that implements Read and Seek to be processed in parallel.
+/// The reader, a File included, goes through ReadRegions::new and is shared behind a lock, so the regions are read
+/// serially, one at a time, and only the splitting of the regions into records runs in parallel.
+/// A File is better given to ReadRegions::from_file, which reads the regions at their offsets so that the worker
+/// threads read at the same time. ReadRegions also allows the size of the regions to be set.
+/// ```
+/// use std::io::Cursor;
+/// use parallel_task::prelude::*;
+///
+/// let text = (0..1_000).map(|val| val.to_string()).collect::<Vec<String>>().join("\n");
+/// let total = Cursor::new(text.into_bytes()).parallel_lines().unwrap()
+/// .map(|line| line.unwrap().parse::<u64>().unwrap())
+/// .sum::<u64>();
+/// assert_eq!(total,(0..1_000).sum::<u64>())
+/// ```
+///
+/// Reading a File without the lock:
+/// ```no_run
+/// use parallel_task::prelude::*;
+/// use parallel_task::iterators::reader::ReadRegions;
+///
+/// let file = std::fs::File::open("data.txt").unwrap();
+/// let count = ReadRegions::from_file(file).unwrap().lines().count();
+/// ```
+pub trait ParallelRead: Read + Seek + Send + Sized {
+    fn parallel_lines(self) -> io::Result<ParallelLines<Self>> {
+        Ok(ReadRegions::new(self)?.lines())
+    }
+
+    fn parallel_records(self, delimiter:u8) -> io::Result<ParallelRecords<Self>> {
+        Ok(ReadRegions::new(self)?.with_delimiter(delimiter).records())
+    }
+}
+
+impl<R> ParallelRead for R
+where R: Read + Seek + Send {}
+
@@ -0,0 +1,250 @@
+//! StrPieces allows the chars, bytes, lines or split segments of a str to be processed in parallel. The queue cuts the
+//! str into pieces at char boundaries, or just after a delimiter for lines and split, and the pieces are broken down into
+//! their chars, bytes, lines or segments within the worker threads. The outputs keep the order of the str.
+
+use crate::iterators::prelude::{DiscreteQueue, ParallelIterator};
+use crate::map::ParallelMap;
+use crate::pipeline::Pipeline;
+
+const PIECE_SIZE:usize = 4096;
+
+/// Chars of a str, run in parallel
+pub type ParallelChars<'data> = ParallelMap<StrPiece<'data>,Chars,char,ParallelIterator<StrPieces<'data,CharBoundary>,StrPiece<'data>>>;
+
+/// Bytes of a str, run in parallel
+pub type ParallelBytes<'data> = ParallelMap<StrPiece<'data>,Bytes,u8,ParallelIterator<StrPieces<'data,CharBoundary>,StrPiece<'data>>>;
+
+/// Lines of a str, run in parallel
+pub type ParallelStrLines<'data> = ParallelMap<StrPiece<'data>,Lines,&'data str,ParallelIterator<StrPieces<'data,char>,StrPiece<'data>>>;
+
+/// Segments of a str split by a pattern, run in parallel
+pub type ParallelSplit<'data,P> = ParallelMap<StrPiece<'data>,Split<P>,&'data str,ParallelIterator<StrPieces<'data,P>,StrPiece<'data>>>;
+
+/// SplitPattern is a char, or a check on chars, on which a str may be split
+pub trait SplitPattern: Copy + Send + Sync {
+    fn is_match(&self, ch:char) -> bool;
+}
+
+impl SplitPattern for char {
+    fn is_match(&self, ch:char) -> bool {
+        *self == ch
+    }
+}
+
+impl<F> SplitPattern for F
+where F: Fn(char) -> bool + Copy + Send + Sync
+{
+    fn is_match(&self, ch:char) -> bool {
+        self(ch)
+    }
+}
+
+/// Boundary gives the positions at which the str may be cut into pieces
+pub trait Boundary: Copy + Send + Sync {
+    /// First position at or after from where the text may be cut
+    fn next_cut(&self, text:&str, from:usize) -> Option<usize>;
+}
+
+/// Any char boundary, used for chars and bytes
+#[derive(Clone, Copy)]
+pub struct CharBoundary;
+
+impl Boundary for CharBoundary {
+    fn next_cut(&self, text:&str, from:usize) -> Option<usize> {
+        (from..text.len()).find(|pos| text.is_char_boundary(*pos))
+    }
+}
+
+/// Cuts are placed just after a char matching the pattern, so that each piece but the last ends with the delimiter
+impl<P> Boundary for P
+where P: SplitPattern
+{
+    fn next_cut(&self, text:&str, from:usize) -> Option<usize> {
+        let from = (from..text.len()).find(|pos| text.is_char_boundary(*pos))?;
+        text[from..].char_indices()
+        .find(|(_,ch)| self.is_match(*ch))
+        .map(|(pos,ch)| from + pos + ch.len_utf8())
+    }
+}
+
+/// Piece of a str, which knows whether it is the last piece
+pub struct StrPiece<'data> {
+    text:&'data str,
+    last:bool
+}
+
+pub struct StrPieces<'data,B>
+where B: Boundary
+{
+    text:&'data str,
+    start:usize,
+    boundary:B,
+    done:bool
+}
+
+impl<'data,B> StrPieces<'data,B>
+where B: Boundary
+{
+    pub fn new(text:&'data str, boundary:B) -> Self {
+        Self {
+            text,
+            start:0,
+            boundary,
+            done:false
+        }
+    }
+}
+
+impl<'data,B> DiscreteQueue for StrPieces<'data,B>
+where B: Boundary
+{
+    type Output = StrPiece<'data>;
+
+    fn pop(&mut self) -> Option<Self::Output> {
+        if self.done {
+            return None;
+        }
+        // A cut at the end of the text still leaves an empty last piece, which gives the trailing segment of split
+        match self.boundary.next_cut(self.text, self.start + PIECE_SIZE).filter(|cut| *cut < self.text.len()) {
+            Some(cut) => {
+                let text = &self.text[self.start..cut];
+                self.start = cut;
+                Some(StrPiece { text, last: false })
+            }
+            None => {
+                self.done = true;
+                Some(StrPiece { text: &self.text[self.start..], last: true })
+            }
+        }
+    }
+
+    fn pull(&mut self) -> Option<Vec<Self::Output>> {
+        let mut res = Vec::new();
+        while let Some(piece) = self.pop() {
+            res.push(piece);
+        }
+        if res.is_empty() { None } else { Some(res) }
+    }
+
+    fn is_active(&self) -> bool {
+        !self.done
+    }
+
+    fn len(&self) -> Option<usize> {
+        Some((self.text.len() - self.start) / PIECE_SIZE + 1)
+    }
+}
+
+/// Chars breaks a piece into its chars within the worker thread
+pub struct Chars;
+
+impl<'data> Pipeline<StrPiece<'data>> for Chars {
+    type Output = char;
+    type State = ();
+
+    fn init_state(&self) -> Self::State {}
+
+    fn feed<S>(&self, _:&mut Self::State, value:StrPiece<'data>, sink:&mut S) -> bool
+    where S: FnMut(Self::Output) -> bool {
+        value.text.chars().all(sink)
+    }
+}
+
+/// Bytes breaks a piece into its bytes within the worker thread
+pub struct Bytes;
+
+impl<'data> Pipeline<StrPiece<'data>> for Bytes {
+    type Output = u8;
+    type State = ();
+
+    fn init_state(&self) -> Self::State {}
+
+    fn feed<S>(&self, _:&mut Self::State, value:StrPiece<'data>, sink:&mut S) -> bool
+    where S: FnMut(Self::Output) -> bool {
+        value.text.bytes().all(sink)
+    }
+}
+
+/// Lines breaks a piece into its lines within the worker thread, as `str::lines` does
+pub struct Lines;
+
+impl<'data> Pipeline<StrPiece<'data>> for Lines {
+    type Output = &'data str;
+    type State = ();
+
+    fn init_state(&self) -> Self::State {}
+
+    fn feed<S>(&self, _:&mut Self::State, value:StrPiece<'data>, sink:&mut S) -> bool
+    where S: FnMut(Self::Output) -> bool {
+        value.text.lines().all(sink)
+    }
+}
+
+/// Split breaks a piece into the segments between the delimiters within the worker thread, as `str::split` does
+pub struct Split<P> {
+    pattern:P
+}
+
+impl<'data,P> Pipeline<StrPiece<'data>> for Split<P>
+where P: SplitPattern
+{
+    type Output = &'data str;
+    type State = ();
+
+    fn init_state(&self) -> Self::State {}
+
+    fn feed<S>(&self, _:&mut Self::State, value:StrPiece<'data>, sink:&mut S) -> bool
+    where S: FnMut(Self::Output) -> bool {
+        let pattern = self.pattern;
+        // Each piece but the last ends with a delimiter, which does not start a new segment
+        if value.last {
+            value.text.split(|ch| pattern.is_match(ch)).all(sink)
+        } else {
+            value.text.split_terminator(|ch| pattern.is_match(ch)).all(sink)
+        }
+    }
+}
+
+/// ParallelStr allows the chars, bytes, lines or split segments of a str or String to be processed in parallel.
+/// The chars may be collected back into a String.
+/// ```
+/// use parallel_task::prelude::*;
+///
+/// let text = "parallel task";
+/// let res = text.parallel_chars().map(|ch| ch.to_ascii_uppercase()).collect::<String>();
+/// assert_eq!(res,"PARALLEL TASK");
+/// let res = "a,b,,c".parallel_split(',').map(|val| val.len()).collect::<Vec<usize>>();
+/// assert_eq!(res,vec![1,1,0,1])
+/// ```
+pub trait ParallelStr<'data> {
+    fn parallel_chars(&'data self) -> ParallelChars<'data>;
+
+    fn parallel_bytes(&'data self) -> ParallelBytes<'data>;
+
+    /// Lines are split on newline, and a trailing carriage return is removed, as with `str::lines`
+    fn parallel_lines(&'data self) -> ParallelStrLines<'data>;
+
+    /// Splits on each char matching the pattern, as with `str::split`
+    fn parallel_split<P>(&'data self, pattern:P) -> ParallelSplit<'data,P>
+    where P: SplitPattern;
+}
+
+impl<'data> ParallelStr<'data> for str {
+    fn parallel_chars(&'data self) -> ParallelChars<'data> {
+        ParallelMap::new(ParallelIterator::new(StrPieces::new(self, CharBoundary)), Chars)
+    }
+
+    fn parallel_bytes(&'data self) -> ParallelBytes<'data> {
+        ParallelMap::new(ParallelIterator::new(StrPieces::new(self, CharBoundary)), Bytes)
+    }
+
+    fn parallel_lines(&'data self) -> ParallelStrLines<'data> {
+        ParallelMap::new(ParallelIterator::new(StrPieces::new(self, '\n')), Lines)
+    }
+
+    fn parallel_split<P>(&'data self, pattern:P) -> ParallelSplit<'data,P>
+    where P: SplitPattern
+    {
+        ParallelMap::new(ParallelIterator::new(StrPieces::new(self, pattern)), Split { pattern })
+    }
+}
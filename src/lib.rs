@@ -28,7 +28,17 @@
 //! ```
 //! 
 pub mod map;
+pub mod map_init;
+pub mod try_map;
+pub mod filter;
+pub mod flat_map;
+pub mod indexed;
+pub mod reduce;
+pub mod search;
+pub mod unzip;
+pub mod pipeline;
 pub mod collector;
+pub mod collect_into;
 pub mod worker_thread;
 pub mod errors;
 pub mod prelude;
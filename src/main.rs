@@ -5,17 +5,15 @@ fn main() {
     //Map being tested....
     println!("Map Samples");
     //Samples with both parallel_iter and into_parallel_iter options below    
-    let mut res = (0..30).collect::<Vec<_>>()
+    let res = (0..30).collect::<Vec<_>>()
                                                 .parallel_iter()
                                                 .map(|i| {println!("{}",i);*i})
                                                 .collect::<Vec<_>>();    
-    res.sort();
     
     println!("res = {:?}",res);
-    let mut res2 = res.into_parallel_iter()
+    let res2 = res.into_parallel_iter()
     .map(|i| {println!("{}",i);i})
     .collect::<Vec<_>>();  
-    res2.sort();    
     
     // For each being tested....
     println!("For Each test");
@@ -1,8 +1,10 @@
-//! ParallelMap is a structure type that captures the Map object and function necessary to run the values within the AtomicIterator in parallel.
+//! ParallelMap is a structure type that captures the pipeline of map, filter and flat_map adapters along with the AtomicIterator, so that
+//! the chained adapters are run on the values in parallel within a single pass.
 
 use std::marker::PhantomData;
+use crate::pipeline::{Filter, FilterMap, FlatMap, Map, Pipeline, TakeWhileAny};
 use crate::task_queue::TaskQueue;
-use crate::utils;
+use crate::push_workers::thread_pool::ThreadPool;
 use crate::worker_thread::WorkerThreads;
 use super::iterators::iterator::*;
 
@@ -35,13 +37,14 @@ T:Send + Sync {}
 
 pub struct ParallelMap<V,F,T,I>
 where I: AtomicIterator<AtomicItem = V> + Send + Sized,
-F: Fn(V) -> T + Send + Sync,
+F: Pipeline<V,Output=T>,
 V: Send,
 T:Send 
 {
     pub iter: TaskQueue<I,V>,
     pub f:F,
     pub num_threads:usize,
+    pub pool: ThreadPool,
     pub v: PhantomData<V>,
     pub t: PhantomData<T>,
 }
@@ -59,7 +62,7 @@ T:Send
 #[allow(dead_code)]
 impl<I,V,F,T> ParallelMap<V,F,T,I>
 where I:AtomicIterator<AtomicItem = V> + Send + Sized,
-F: Fn(V) -> T + Send + Sync,
+F: Pipeline<V,Output=T>,
 V: Send + Sync,
 T:Send + Sync
 {
@@ -68,32 +71,180 @@ T:Send + Sync
         Self {
             iter: TaskQueue { iter },
             f,
-            num_threads: Self::max_threads(),
+            num_threads: ThreadPool::global().max_threads(),
+            pool: ThreadPool::global().clone(),
             v: PhantomData,
             t: PhantomData    
         }
     }
 
-    /// Available_parallelism() function gives an idea of the CPUs available. 
-    fn max_threads() -> usize {        
-        utils::max_threads()
+    /// Set the maximum number of threads used for running the parallel tasker. This is capped to the size of the pool.
+    pub fn threads(mut self, nthreads:usize) -> Self {
+        self.num_threads = usize::min(self.pool.max_threads(),nthreads);
+        self
     }
 
-    /// Set the thread pool size for running the parallel tasker.    
-    pub fn threads(mut self, nthreads:usize) -> Self {
-        self.num_threads = usize::min(Self::max_threads(),nthreads);
+    /// Run the tasks on the given pool instead of the global pool.
+    pub fn pool(mut self, pool:&ThreadPool) -> Self {
+        self.num_threads = if self.num_threads == self.pool.max_threads() {
+            pool.max_threads()
+        } else {
+            usize::min(self.num_threads,pool.max_threads())
+        };
+        self.pool = pool.clone();
         self
     }
 
-    /// Collect the results of the Map in a type implementing Collector trait    
+    /// Collect the results of the Map in a type implementing Collector trait. The results are collected
+    /// in the same order as the values in the source, just as with `iter().map().collect()`.
+    /// Results and Options may be collected into `Result<C,E>` or `Option<C>`, in which case the first Err or None
+    /// is given and the values after it are skipped.
+    /// ```
+    /// use parallel_task::prelude::*;
+    /// 
+    /// let res = (0..100_000).collect::<Vec<i32>>().parallel_iter().map(|val|val * 2).collect::<Vec<i32>>();
+    /// assert_eq!(res,(0..100_000).map(|val|val * 2).collect::<Vec<i32>>());
+    /// let res = vec!["1","2","x"].parallel_iter().map(|val|val.parse::<i32>().ok()).collect::<Option<Vec<i32>>>();
+    /// assert_eq!(res,None)
+    /// ```
+    /// 
     pub fn collect<C>(self) -> C
-    where C: Collector<T>
+    where C: CollectTarget<T>
     {                
         let num_threads = self.num_threads;        
 
-        WorkerThreads { nthreads: num_threads }
-        .collect(self)      
-        
+        WorkerThreads { nthreads: num_threads, pool: self.pool.clone() }
+        .collect(self)
+
+    }
+
+    /// Run f on each output. This is chained with the previous adapters and runs within the same worker threads.
+    /// ```
+    /// use parallel_task::prelude::*;
+    ///
+    /// let res = (0..100).collect::<Vec<i32>>().parallel_iter().map(|val|*val).map(|val|val + 1).collect::<Vec<i32>>();
+    /// assert_eq!(res,(1..101).collect::<Vec<i32>>())
+    /// ```
+    ///
+    pub fn map<G,U>(self, f:G) -> ParallelMap<V,Map<F,G>,U,I>
+    where G: Fn(T) -> U + Send + Sync,
+    U: Send + Sync
+    {
+        self.chain(|pipeline| Map::new(pipeline, f))
+    }
+
+    /// Keep only the outputs for which the predicate is true.
+    /// ```
+    /// use parallel_task::prelude::*;
+    ///
+    /// let res = (0..100).collect::<Vec<i32>>().parallel_iter().map(|val|*val).filter(|val|val % 2 == 0).collect::<Vec<i32>>();
+    /// assert_eq!(res,(0..100).step_by(2).collect::<Vec<i32>>())
+    /// ```
+    ///
+    pub fn filter<P>(self, predicate:P) -> ParallelMap<V,Filter<F,P>,T,I>
+    where P: Fn(&T) -> bool + Send + Sync
+    {
+        self.chain(|pipeline| Filter::new(pipeline, predicate))
+    }
+
+    /// Run f on each output and keep the Some values.
+    /// ```
+    /// use parallel_task::prelude::*;
+    ///
+    /// let res = vec!["1","x","3"].parallel_iter().map(|val|val.parse::<i32>()).filter_map(|val|val.ok()).collect::<Vec<i32>>();
+    /// assert_eq!(res,vec![1,3])
+    /// ```
+    ///
+    pub fn filter_map<G,U>(self, f:G) -> ParallelMap<V,FilterMap<F,G>,U,I>
+    where G: Fn(T) -> Option<U> + Send + Sync,
+    U: Send + Sync
+    {
+        self.chain(|pipeline| FilterMap::new(pipeline, f))
+    }
+
+    /// Run f on each output and flatten the returned IntoIterator. The items keep the order they are given in.
+    /// ```
+    /// use parallel_task::prelude::*;
+    ///
+    /// let res = vec![1,2,3].parallel_iter().map(|val|*val).flat_map(|val|vec![val;val]).collect::<Vec<usize>>();
+    /// assert_eq!(res,vec![1,2,2,3,3,3])
+    /// ```
+    ///
+    pub fn flat_map<G,U>(self, f:G) -> ParallelMap<V,FlatMap<F,G>,U::Item,I>
+    where G: Fn(T) -> U + Send + Sync,
+    U: IntoIterator,
+    U::Item: Send + Sync
+    {
+        self.chain(|pipeline| FlatMap::new(pipeline, f))
+    }
+
+    /// Keep the outputs while the predicate holds. Once it fails for an output, all the threads are stopped without
+    /// processing the values remaining in their queues. The outputs kept need not be all of those before the failing one.
+    /// ```
+    /// use parallel_task::prelude::*;
+    ///
+    /// let res = (0..100_000).collect::<Vec<i32>>().parallel_iter().map(|val|*val).take_while_any(|val|*val < 50_000).collect::<Vec<i32>>();
+    /// assert!(res.iter().all(|val| *val < 50_000))
+    /// ```
+    ///
+    pub fn take_while_any<P>(self, predicate:P) -> ParallelMap<V,TakeWhileAny<F,P>,T,I>
+    where P: Fn(&T) -> bool + Send + Sync
+    {
+        self.chain(|pipeline| TakeWhileAny::new(pipeline, predicate))
+    }
+
+    /// Run f on each output within the worker threads.
+    /// ```
+    /// use parallel_task::prelude::*;
+    ///
+    /// (0..100).collect::<Vec<i32>>().parallel_iter().filter(|val|**val > 50).for_each(|val|{ print!(" {}",val);});
+    /// ```
+    ///
+    pub fn for_each<G>(self, f:G)
+    where G: Fn(T) + Send + Sync
+    {
+        self.map(f).collect::<Vec<()>>();
+    }
+
+    /// Extend the pipeline with an adapter while keeping the source and the thread settings
+    pub(crate) fn chain<P,U>(self, adapter:impl FnOnce(F) -> P) -> ParallelMap<V,P,U,I>
+    where P: Pipeline<V,Output=U>,
+    U: Send + Sync
+    {
+        ParallelMap {
+            iter: self.iter,
+            f: adapter(self.f),
+            num_threads: self.num_threads,
+            pool: self.pool,
+            v: PhantomData,
+            t: PhantomData
+        }
     }
 }
 
+
+impl<I,V,F,T,E> ParallelMap<V,F,Result<T,E>,I>
+where I:AtomicIterator<AtomicItem = V> + Send + Sized,
+F: Pipeline<V,Output=Result<T,E>>,
+V: Send + Sync,
+T:Send + Sync,
+E:Send + Sync
+{
+    /// Collect the results of a fallible Map. Once an Err is found the values after it are skipped by all threads,
+    /// and the first Err in the order of the source is returned. Otherwise the Ok values are collected in order in
+    /// a type implementing Collector trait. This is the same as collecting into `Result<C,E>`.
+    /// ```
+    /// use parallel_task::prelude::*;
+    /// 
+    /// let res = (0..100_000).collect::<Vec<i32>>().parallel_iter()
+    /// .map(|val| if *val == 50_000 { Err(format!("failed at {}",val)) } else { Ok(*val) })
+    /// .try_collect::<Vec<i32>>();
+    /// assert_eq!(res,Err("failed at 50000".to_owned()))
+    /// ```
+    /// 
+    pub fn try_collect<C>(self) -> Result<C,E>
+    where C: CollectTarget<T>
+    {
+        self.collect::<Result<C,E>>()
+    }
+}
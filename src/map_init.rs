@@ -0,0 +1,102 @@
+//! map_init, map_with and for_each_init give each worker thread its own state, such as a scratch buffer, an RNG or a
+//! connection, which is passed as `&mut` to every call of the closure on that thread. The state is created when the
+//! thread gets its first value, so threads that are never given a value do not create one. It is kept till the thread
+//! is done, so it is neither rebuilt per value nor shared behind a lock.
+
+use crate::map::ParallelMap;
+use crate::pipeline::{Identity, MapInit, MapWith, Pipeline};
+use super::iterators::iterator::*;
+
+/// map_init, map_with and for_each_init on ParallelMap. They are chained with the previous adapters and run within the
+/// same worker threads.
+/// ```
+/// use parallel_task::prelude::*;
+///
+/// let res = (0..1_000).collect::<Vec<usize>>().parallel_iter().map(|val|*val)
+/// .map_init(Vec::new, |buf:&mut Vec<usize>, val| { buf.clear(); buf.extend(0..val % 10); buf.len() })
+/// .collect::<Vec<usize>>();
+/// assert_eq!(res,(0..1_000).map(|val| val % 10).collect::<Vec<usize>>());
+///
+/// let res = vec![1,2,3].parallel_iter().map(|val|*val).map_with(10, |offset, val| val + *offset).collect::<Vec<i32>>();
+/// assert_eq!(res,vec![11,12,13])
+/// ```
+///
+impl<I,V,F,T> ParallelMap<V,F,T,I>
+where I:AtomicIterator<AtomicItem = V> + Send + Sized,
+F: Pipeline<V,Output=T>,
+V: Send + Sync,
+T:Send + Sync
+{
+    /// Run f on each output along with the state of the thread, which is created by init once per thread
+    pub fn map_init<INIT,W,G,U>(self, init:INIT, f:G) -> ParallelMap<V,MapInit<F,INIT,G>,U,I>
+    where INIT: Fn() -> W + Send + Sync,
+    G: Fn(&mut W, T) -> U + Send + Sync,
+    U: Send + Sync
+    {
+        self.chain(|pipeline| MapInit::new(pipeline, init, f))
+    }
+
+    /// Run f on each output along with a clone of the seed, which is made once per thread
+    pub fn map_with<W,G,U>(self, seed:W, f:G) -> ParallelMap<V,MapWith<F,W,G>,U,I>
+    where W: Clone + Send + Sync,
+    G: Fn(&mut W, T) -> U + Send + Sync,
+    U: Send + Sync
+    {
+        self.chain(|pipeline| MapWith::new(pipeline, seed, f))
+    }
+
+    /// Run f on each output along with the state of the thread, which is created by init once per thread
+    pub fn for_each_init<INIT,W,G>(self, init:INIT, f:G)
+    where INIT: Fn() -> W + Send + Sync,
+    G: Fn(&mut W, T) + Send + Sync
+    {
+        self.map_init(init, f).collect::<Vec<()>>();
+    }
+}
+
+/// ParallelMapInitIter allows map_init, map_with and for_each_init to be called directly on type implementing
+/// AtomicIterator.
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use parallel_task::prelude::*;
+///
+/// let inits = AtomicUsize::new(0);
+/// let res = (0..10_000u32).into_parallel_iter()
+/// .map_init(|| { inits.fetch_add(1, Ordering::Relaxed); String::new() }, |buf:&mut String, val| {
+///     buf.clear();
+///     buf.push_str(&val.to_string());
+///     buf.len()
+/// })
+/// .collect::<Vec<usize>>();
+/// assert_eq!(res.len(),10_000);
+/// assert!(inits.load(Ordering::Relaxed) <= ThreadPool::global().max_threads());
+/// ```
+///
+pub trait ParallelMapInitIter<V>
+where Self: AtomicIterator<AtomicItem = V> + Send + Sized,
+V: Send + Sync
+{
+    fn map_init<INIT,W,G,U>(self, init:INIT, f:G) -> ParallelMap<V,MapInit<Identity<V>,INIT,G>,U,Self>
+    where INIT: Fn() -> W + Send + Sync,
+    G: Fn(&mut W, V) -> U + Send + Sync,
+    U: Send + Sync {
+        ParallelMap::new(self, Identity::default()).map_init(init, f)
+    }
+
+    fn map_with<W,G,U>(self, seed:W, f:G) -> ParallelMap<V,MapWith<Identity<V>,W,G>,U,Self>
+    where W: Clone + Send + Sync,
+    G: Fn(&mut W, V) -> U + Send + Sync,
+    U: Send + Sync {
+        ParallelMap::new(self, Identity::default()).map_with(seed, f)
+    }
+
+    fn for_each_init<INIT,W,G>(self, init:INIT, f:G)
+    where INIT: Fn() -> W + Send + Sync,
+    G: Fn(&mut W, V) + Send + Sync {
+        ParallelMap::new(self, Identity::default()).for_each_init(init, f)
+    }
+}
+
+impl<I,V> ParallelMapInitIter<V> for I
+where I: AtomicIterator<AtomicItem = V> + Send + Sized,
+V: Send + Sync {}
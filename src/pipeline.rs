@@ -0,0 +1,328 @@
+//! Pipeline captures the chain of map, filter and other adapters that is run on each value within the worker threads.
+//! A closure Fn(V) -> T is the simplest pipeline, giving exactly one output per value. Adapters such as Filter or FlatMap
+//! wrap a pipeline to drop values or to give several outputs for a value. This allows a chain such as
+//! filter(..).map(..) to run as a single parallel pass.
+
+use std::marker::PhantomData;
+
+/// Pipeline is run by the ThreadRunner on every value popped from its queue.
+pub trait Pipeline<V>: Send + Sync {
+    type Output;
+
+    /// State kept by each ThreadRunner across all the values it runs, such as that of map_init
+    type State;
+
+    /// Creates the state of a ThreadRunner. This is called once by each runner before its first value.
+    fn init_state(&self) -> Self::State;
+
+    /// Runs the pipeline on the value and passes each output to the sink. The sink returns false to stop
+    /// any further outputs, in which case feed returns false as well.
+    fn feed<S>(&self, state:&mut Self::State, value:V, sink:&mut S) -> bool
+    where S: FnMut(Self::Output) -> bool;
+}
+
+impl<V,T,F> Pipeline<V> for F
+where F: Fn(V) -> T + Send + Sync
+{
+    type Output = T;
+    type State = ();
+
+    fn init_state(&self) -> Self::State {}
+
+    fn feed<S>(&self, _:&mut Self::State, value:V, sink:&mut S) -> bool
+    where S: FnMut(Self::Output) -> bool {
+        sink(self(value))
+    }
+}
+
+/// OneToOne is implemented by the pipelines that give exactly one output for every value, so that each output
+/// may be written to the position of its value in the source. Pipelines with filters or flat maps may not be
+/// collected into a slice.
+///
+/// # Safety
+///
+/// `feed` must pass exactly one output to the sink for every value, unless it panics. Collecting into a Vec relies
+/// on this to leave no position unwritten before the length of the Vec is set, so a pipeline giving two outputs for
+/// one value and none for another would expose uninitialised memory.
+/// ```compile_fail
+/// use parallel_task::prelude::*;
+///
+/// let mut out = Vec::new();
+/// (0..100).into_parallel_iter().filter(|val| val % 2 == 0).collect_into_vec(&mut out);
+/// ```
+pub unsafe trait OneToOne<V>: Pipeline<V> {}
+
+// SAFETY: the closure gives one output for the value, which feed passes to the sink
+unsafe impl<V,T,F> OneToOne<V> for F
+where F: Fn(V) -> T + Send + Sync {}
+
+/// Pipeline that passes on the values as is. It is the start of the adapters called directly on an AtomicIterator.
+pub struct Identity<V>(PhantomData<fn(V) -> V>);
+
+impl<V> Default for Identity<V> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<V> Pipeline<V> for Identity<V> {
+    type Output = V;
+    type State = ();
+
+    fn init_state(&self) -> Self::State {}
+
+    fn feed<S>(&self, _:&mut Self::State, value:V, sink:&mut S) -> bool
+    where S: FnMut(Self::Output) -> bool {
+        sink(value)
+    }
+}
+
+// SAFETY: the value is passed to the sink as is
+unsafe impl<V> OneToOne<V> for Identity<V> {}
+
+/// Runs f on each output of the pipeline
+pub struct Map<P,F> {
+    pipeline: P,
+    f: F
+}
+
+impl<P,F> Map<P,F> {
+    pub fn new(pipeline:P, f:F) -> Self {
+        Self { pipeline, f }
+    }
+}
+
+impl<V,P,F,T> Pipeline<V> for Map<P,F>
+where P: Pipeline<V>,
+F: Fn(P::Output) -> T + Send + Sync
+{
+    type Output = T;
+    type State = P::State;
+
+    fn init_state(&self) -> Self::State {
+        self.pipeline.init_state()
+    }
+
+    fn feed<S>(&self, state:&mut Self::State, value:V, sink:&mut S) -> bool
+    where S: FnMut(Self::Output) -> bool {
+        self.pipeline.feed(state, value, &mut |val| sink((self.f)(val)))
+    }
+}
+
+// SAFETY: f is run once on each output of a OneToOne pipeline
+unsafe impl<V,P,F,T> OneToOne<V> for Map<P,F>
+where P: OneToOne<V>,
+F: Fn(P::Output) -> T + Send + Sync {}
+
+/// Passes on only the outputs of the pipeline for which the predicate is true
+pub struct Filter<P,F> {
+    pipeline: P,
+    predicate: F
+}
+
+impl<P,F> Filter<P,F> {
+    pub fn new(pipeline:P, predicate:F) -> Self {
+        Self { pipeline, predicate }
+    }
+}
+
+impl<V,P,F> Pipeline<V> for Filter<P,F>
+where P: Pipeline<V>,
+F: Fn(&P::Output) -> bool + Send + Sync
+{
+    type Output = P::Output;
+    type State = P::State;
+
+    fn init_state(&self) -> Self::State {
+        self.pipeline.init_state()
+    }
+
+    fn feed<S>(&self, state:&mut Self::State, value:V, sink:&mut S) -> bool
+    where S: FnMut(Self::Output) -> bool {
+        self.pipeline.feed(state, value, &mut |val| {
+            if (self.predicate)(&val) { sink(val) } else { true }
+        })
+    }
+}
+
+/// Runs f on each output of the pipeline and passes on the Some values
+pub struct FilterMap<P,F> {
+    pipeline: P,
+    f: F
+}
+
+impl<P,F> FilterMap<P,F> {
+    pub fn new(pipeline:P, f:F) -> Self {
+        Self { pipeline, f }
+    }
+}
+
+impl<V,P,F,T> Pipeline<V> for FilterMap<P,F>
+where P: Pipeline<V>,
+F: Fn(P::Output) -> Option<T> + Send + Sync
+{
+    type Output = T;
+    type State = P::State;
+
+    fn init_state(&self) -> Self::State {
+        self.pipeline.init_state()
+    }
+
+    fn feed<S>(&self, state:&mut Self::State, value:V, sink:&mut S) -> bool
+    where S: FnMut(Self::Output) -> bool {
+        self.pipeline.feed(state, value, &mut |val| {
+            match (self.f)(val) {
+                Some(output) => sink(output),
+                None => true
+            }
+        })
+    }
+}
+
+/// Runs f on each output of the pipeline and passes on every item of the returned IntoIterator
+pub struct FlatMap<P,F> {
+    pipeline: P,
+    f: F
+}
+
+impl<P,F> FlatMap<P,F> {
+    pub fn new(pipeline:P, f:F) -> Self {
+        Self { pipeline, f }
+    }
+}
+
+impl<V,P,F,U> Pipeline<V> for FlatMap<P,F>
+where P: Pipeline<V>,
+F: Fn(P::Output) -> U + Send + Sync,
+U: IntoIterator
+{
+    type Output = U::Item;
+    type State = P::State;
+
+    fn init_state(&self) -> Self::State {
+        self.pipeline.init_state()
+    }
+
+    fn feed<S>(&self, state:&mut Self::State, value:V, sink:&mut S) -> bool
+    where S: FnMut(Self::Output) -> bool {
+        self.pipeline.feed(state, value, &mut |val| {
+            (self.f)(val).into_iter().all(&mut *sink)
+        })
+    }
+}
+
+/// Passes on the outputs of the pipeline while the predicate is true. The first output failing it stops the runner,
+/// which in turn stops all the other runners.
+pub struct TakeWhileAny<P,F> {
+    pipeline: P,
+    predicate: F
+}
+
+impl<P,F> TakeWhileAny<P,F> {
+    pub fn new(pipeline:P, predicate:F) -> Self {
+        Self { pipeline, predicate }
+    }
+}
+
+impl<V,P,F> Pipeline<V> for TakeWhileAny<P,F>
+where P: Pipeline<V>,
+F: Fn(&P::Output) -> bool + Send + Sync
+{
+    type Output = P::Output;
+    type State = P::State;
+
+    fn init_state(&self) -> Self::State {
+        self.pipeline.init_state()
+    }
+
+    fn feed<S>(&self, state:&mut Self::State, value:V, sink:&mut S) -> bool
+    where S: FnMut(Self::Output) -> bool {
+        self.pipeline.feed(state, value, &mut |val| {
+            (self.predicate)(&val) && sink(val)
+        })
+    }
+}
+
+/// Runs f on each output of the pipeline along with the state of the runner, which is created by init when the
+/// runner gets its first value. A runner that gets no values does not call init.
+pub struct MapInit<P,INIT,F> {
+    pipeline: P,
+    init: INIT,
+    f: F
+}
+
+impl<P,INIT,F> MapInit<P,INIT,F> {
+    pub fn new(pipeline:P, init:INIT, f:F) -> Self {
+        Self { pipeline, init, f }
+    }
+}
+
+impl<V,P,INIT,F,W,T> Pipeline<V> for MapInit<P,INIT,F>
+where P: Pipeline<V>,
+INIT: Fn() -> W + Send + Sync,
+F: Fn(&mut W, P::Output) -> T + Send + Sync
+{
+    type Output = T;
+    type State = (P::State, Option<W>);
+
+    fn init_state(&self) -> Self::State {
+        (self.pipeline.init_state(), None)
+    }
+
+    fn feed<S>(&self, state:&mut Self::State, value:V, sink:&mut S) -> bool
+    where S: FnMut(Self::Output) -> bool {
+        let (inner, own) = state;
+        self.pipeline.feed(inner, value, &mut |val| {
+            let own = own.get_or_insert_with(|| (self.init)());
+            sink((self.f)(own, val))
+        })
+    }
+}
+
+// SAFETY: f is run once on each output of a OneToOne pipeline
+unsafe impl<V,P,INIT,F,W,T> OneToOne<V> for MapInit<P,INIT,F>
+where P: OneToOne<V>,
+INIT: Fn() -> W + Send + Sync,
+F: Fn(&mut W, P::Output) -> T + Send + Sync {}
+
+/// Runs f on each output of the pipeline along with a clone of the seed, which is made when the runner gets its
+/// first value
+pub struct MapWith<P,W,F> {
+    pipeline: P,
+    seed: W,
+    f: F
+}
+
+impl<P,W,F> MapWith<P,W,F> {
+    pub fn new(pipeline:P, seed:W, f:F) -> Self {
+        Self { pipeline, seed, f }
+    }
+}
+
+impl<V,P,W,F,T> Pipeline<V> for MapWith<P,W,F>
+where P: Pipeline<V>,
+W: Clone + Send + Sync,
+F: Fn(&mut W, P::Output) -> T + Send + Sync
+{
+    type Output = T;
+    type State = (P::State, Option<W>);
+
+    fn init_state(&self) -> Self::State {
+        (self.pipeline.init_state(), None)
+    }
+
+    fn feed<S>(&self, state:&mut Self::State, value:V, sink:&mut S) -> bool
+    where S: FnMut(Self::Output) -> bool {
+        let (inner, own) = state;
+        self.pipeline.feed(inner, value, &mut |val| {
+            let own = own.get_or_insert_with(|| self.seed.clone());
+            sink((self.f)(own, val))
+        })
+    }
+}
+
+// SAFETY: f is run once on each output of a OneToOne pipeline
+unsafe impl<V,P,W,F,T> OneToOne<V> for MapWith<P,W,F>
+where P: OneToOne<V>,
+W: Clone + Send + Sync,
+F: Fn(&mut W, P::Output) -> T + Send + Sync {}
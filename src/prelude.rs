@@ -2,9 +2,22 @@
 //! functionalities.
 //! 
 
-pub use crate::iterators::prelude::{AtomicIterator,ParallelIter,IntoParallelIter};
+pub use crate::iterators::prelude::{AtomicIterator,ExactSizeAtomicIterator,ParallelIter,ParallelIterMut,IntoParallelIter};
+pub use crate::iterators::bridge::IntoParallelBridge;
+pub use crate::iterators::reader::ParallelRead;
+pub use crate::iterators::strings::ParallelStr;
+pub use crate::iterators::chunks::{ParallelSlice,ParallelSliceMut};
 pub use crate::{
     map::ParallelMapIter,
-    for_each::ParallelForEachIter    
+    map_init::ParallelMapInitIter,
+    try_map::ParallelTryMapIter,
+    filter::{ParallelFilterIter,ParallelFilterMapIter},
+    flat_map::ParallelFlatMapIter,
+    indexed::ParallelIndexedIter,
+    reduce::ParallelReduceIter,
+    search::ParallelSearchIter,
+    unzip::{ParallelPartitionIter,ParallelUnzipIter},
+    for_each::{ParallelForEachIter,ParallelTryForEachIter}    
 };
-pub use crate::task_queue::TaskQueue;
\ No newline at end of file
+pub use crate::task_queue::TaskQueue;
+pub use crate::push_workers::thread_pool::{ThreadPool,ThreadPoolBuilder};
\ No newline at end of file
@@ -2,4 +2,5 @@ pub mod worker_thread;
 pub mod worker_controller;
 pub mod thread_runner;
 pub mod thread_manager;
-pub mod priorisation;
\ No newline at end of file
+pub mod priorisation;
+pub mod thread_pool;
\ No newline at end of file
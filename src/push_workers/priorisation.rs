@@ -6,6 +6,8 @@
 
 use std::cmp::Ordering;
 use super::thread_manager::ThreadManager;
+use crate::pipeline::Pipeline;
+use crate::reduce::Reducer;
 
 /// Independent approaches to thread prioritization may be implemented using PrioritizeThread trait.
 /// This is an input parameter when defining the Worker Controller.
@@ -14,11 +16,12 @@ use super::thread_manager::ThreadManager;
 /// a. threadpos - usize (used to pick the thread from the thread_manager)
 /// b. remaining values in the queue - usize (available from WorkerThread)
 pub trait PrioritizeThread {
-    fn prioritize<'env, 'scope,Input,Output,F>(&self, thread_manager:&mut ThreadManager<'env, 'scope,Input,Output,F>) 
+    fn prioritize<'env, 'scope,Input,Output,F,R>(&self, thread_manager:&mut ThreadManager<'env, 'scope,Input,Output,F,R>) 
     -> Vec<(usize, usize)>
     where Input: Send + Sync + 'scope,
     Output: Send + Sync + 'scope,  
-    F: Fn(Input) -> Output + Send + Sync + 'scope,
+    F: Pipeline<Input,Output=Output> + 'scope,
+    R: Reducer<Output> + 'scope,
     'env: 'scope;   
 }
 
@@ -32,11 +35,12 @@ pub enum ThreadPrioritization {
 
 impl PrioritizeThread for ThreadPrioritization {
 
-    fn prioritize<'env, 'scope,Input,Output,F>(&self, thread_manager:&mut ThreadManager<'env, 'scope,Input,Output,F>) 
+    fn prioritize<'env, 'scope,Input,Output,F,R>(&self, thread_manager:&mut ThreadManager<'env, 'scope,Input,Output,F,R>) 
     -> Vec<(usize, usize)>
     where Input: Send + Sync + 'scope,
     Output: Send + Sync + 'scope,  
-    F: Fn(Input) -> Output + Send + Sync + 'scope,
+    F: Pipeline<Input,Output=Output> + 'scope,
+    R: Reducer<Output> + 'scope,
     'env: 'scope
     {
         let mut vec_ranking = thread_manager.threads_as_mutable()
@@ -1,40 +1,59 @@
 //! Thread Manager encapsulates all active worker threads and information on free threads
 
-use std::{collections::VecDeque, sync::{Arc, RwLock}};
+use std::{collections::VecDeque, sync::Arc};
 
-use crate::{collector::Collector, errors::WorkThreadError, push_workers::worker_thread::WorkerThread};
+use crate::{errors::WorkThreadError, pipeline::Pipeline, push_workers::{thread_pool::PoolScope, thread_runner::Halt, worker_thread::WorkerThread}, reduce::Reducer, utils::SpinWait};
 
 
-pub struct ThreadManager<'env, 'scope,Input,Output,F>
+pub struct ThreadManager<'env, 'scope,Input,Output,F,R>
 where Input: Send + Sync + 'scope,
 Output: Send + Sync + 'scope,  
-F: Fn(Input) -> Output + Send + Sync + 'scope,
+F: Pipeline<Input,Output=Output> + 'scope,
+R: Reducer<Output> + 'scope,
 'env: 'scope
 {
-    threads:Vec<WorkerThread<'scope,Input,Output>>,
-    scope:&'scope std::thread::Scope<'scope, 'env>,
+    threads:Vec<WorkerThread<'scope,Input,R::Acc>>,
+    scope:&'scope PoolScope<'scope, 'env>,
     max_threads: usize,
     free_threads: VecDeque<usize>,
-    f:  Arc<RwLock<F>>
+    f:  Arc<F>,
+    reducer: Arc<R>,
+    halt: Option<Halt<Output>>,
+    halted: bool
 }
 
-impl<'env, 'scope,Input,Output,F> ThreadManager<'env, 'scope,Input,Output,F> 
+impl<'env, 'scope,Input,Output,F,R> ThreadManager<'env, 'scope,Input,Output,F,R> 
 where Input: Send + Sync + 'scope,
 Output: Send + Sync + 'scope,
-F: Fn(Input) -> Output + Send + Sync + 'scope,
+F: Pipeline<Input,Output=Output> + 'scope,
+R: Reducer<Output> + 'scope,
 'env: 'scope
 {
 
-    pub fn new(scope: &'scope std::thread::Scope<'scope, 'env>,f: Arc<RwLock<F>>, max_threads:usize) -> Self {
+    pub fn new(scope: &'scope PoolScope<'scope, 'env>,f: Arc<F>, reducer: Arc<R>, max_threads:usize, halt: Option<Halt<Output>>) -> Self {
         Self {
             threads: Vec::new(),
             scope,
             max_threads,
             free_threads:VecDeque::new(),
-            f
+            f,
+            reducer,
+            halt,
+            halted: false
         }
     }
 
+    /// Checks if any runner has asked for all threads to be stopped, or has panicked. If so, the remaining values in all
+    /// the queues are abandoned and every other runner is signalled to unwind.
+    pub fn check_halt(&mut self) -> bool {
+        if !self.halted && self.threads.iter_mut().any(|thread| thread.is_halted() || thread.is_panicked()) {
+            self.threads.iter_mut().for_each(|thread| thread.halt());
+            self.clear_free_threads();
+            self.halted = true;
+        }
+        self.halted
+    }
+
     pub fn has_free_threads(&self) -> bool {
         !self.free_threads.is_empty()
     }
@@ -55,21 +74,21 @@ F: Fn(Input) -> Output + Send + Sync + 'scope,
         self.threads.len()
     }
 
-    pub fn get_mut_thread(&mut self,pos:usize) -> &mut WorkerThread<'scope,Input,Output> {
+    pub fn get_mut_thread(&mut self,pos:usize) -> &mut WorkerThread<'scope,Input,R::Acc> {
         &mut self.threads[pos]
     }
 
-    pub fn threads_as_mutable(&mut self) -> &mut Vec<WorkerThread<'scope,Input,Output>> {
+    pub fn threads_as_mutable(&mut self) -> &mut Vec<WorkerThread<'scope,Input,R::Acc>> {
         &mut self.threads
     } 
 
     pub fn add_thread(&mut self) -> Result<(),WorkThreadError>
     where Input: 'scope,
     Output: 'scope,
-    F: Fn(Input) -> Output + Send + Sync + 'scope,
+    F: Pipeline<Input,Output=Output> + 'scope,
     {                                                               
-        let arc_f_clone: Arc<RwLock<F>> = self.f.clone();       
-        match WorkerThread::launch(self.scope,self.threads.len(), arc_f_clone) {
+        let arc_f_clone: Arc<F> = self.f.clone();       
+        match WorkerThread::launch(self.scope,self.threads.len(), arc_f_clone, self.reducer.clone(), self.halt.clone()) {
             Ok(t) =>  {                                                    
                 self.threads.push(t);  
                 Ok(())                                                                                                        
@@ -80,24 +99,36 @@ F: Fn(Input) -> Output + Send + Sync + 'scope,
         }                              
     } 
 
-    pub fn join_all_threads<C>(&mut self) -> Result<C,WorkThreadError>
+    /// Joins all the threads and returns their accumulators in the order of the source.
+    pub fn join_all_threads(&mut self) -> Result<Vec<R::Acc>,WorkThreadError>
     where 'env: 'scope,     
     Input: Send + Sync + 'scope,
-    Output: Send + Sync + 'scope, 
-    C: Collector<Output>   
+    Output: Send + Sync + 'scope
     {
-        let mut results = C::initialize();   
-        while !self.threads.is_empty() {
-            if let Some(thread) = self.threads.pop() {
-                results.extend(
-                thread.join()
-                .map_err(|_|WorkThreadError::ThreadJoin)?
-                .into_iter()
-            );
-            }            
+        // Wait for all the threads to complete their queues, stopping them early if any runner asks for it
+        SpinWait::loop_while_mut(|| {
+            self.check_halt();
+            !self.threads.iter_mut().all(|thread| thread.is_idle())
+        });
+
+        let mut runs:Vec<(usize,R::Acc)> = Vec::new();   
+        let mut error:Option<WorkThreadError> = None;
+        while let Some(thread) = self.threads.pop() {
+            // All threads are joined even if one has failed
+            match thread.join() {
+                Ok(values) => runs.extend(values),
+                Err(e) => { error.get_or_insert(e); }
+            }
         }        
 
-        Ok(results)
+        if let Some(e) = error {
+            return Err(e);
+        }
+
+        // Each run carries the index in the source it starts at. Sorting on the same restores the input order
+        // irrespective of which thread processed the run or how the queues were redistributed.
+        runs.sort_by_key(|(idx,_)| *idx);
+        Ok(runs.into_iter().map(|(_,acc)| acc).collect())
 
     }
 
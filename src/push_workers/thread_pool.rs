@@ -0,0 +1,387 @@
+//! ThreadPool keeps a set of long lived OS threads that are reused across parallel calls. Spawning an OS thread
+//! for every WorkerThread dominates the run time when many small parallel calls are made, hence the WorkerController
+//! leases threads from a pool instead. Idle threads are parked and are woken up when a new job is handed to them.
+//! Jobs are run within a PoolScope, which like std::thread::scope, waits for all the jobs spawned within it to complete
+//! before returning. This allows the jobs to borrow non 'static data such as the values of a Vec.
+//! Pools are configured via the ThreadPoolBuilder.
+
+use std::{any::Any, marker::PhantomData, panic::{catch_unwind, resume_unwind, AssertUnwindSafe}, sync::{atomic::{AtomicBool, AtomicUsize, Ordering}, mpsc::{sync_channel, Receiver}, Arc, Condvar, Mutex, OnceLock}, thread::Thread};
+
+use crate::{errors::WorkThreadError, utils};
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+static GLOBAL_POOL: OnceLock<ThreadPool> = OnceLock::new();
+
+/// Slot via which a job is handed over to a pool thread
+struct PoolWorker {
+    job: Mutex<Option<Job>>
+}
+
+struct IdleWorker {
+    worker: Arc<PoolWorker>,
+    thread: Thread
+}
+
+/// Registry of the idle threads. This is shared with the pool threads so that they may return themselves
+/// to the idle list once the job is done.
+struct PoolRegistry {
+    idle: Mutex<Vec<IdleWorker>>,
+    shutdown: AtomicBool
+}
+
+impl PoolRegistry {
+    fn pop_idle(&self) -> Option<IdleWorker> {
+        self.idle.lock().unwrap_or_else(|e| e.into_inner()).pop()
+    }
+
+    /// Returns the worker to the idle list. Returns false if the pool is being shut down, in which case
+    /// the thread is expected to exit.
+    fn release(&self, worker:&Arc<PoolWorker>) -> bool {
+        let mut idle = self.idle.lock().unwrap_or_else(|e| e.into_inner());
+        if self.shutdown.load(Ordering::Acquire) {
+            false
+        } else {
+            idle.push(IdleWorker { worker: worker.clone(), thread: std::thread::current() });
+            true
+        }
+    }
+
+    fn is_shutdown(&self) -> bool {
+        self.shutdown.load(Ordering::Acquire)
+    }
+
+    fn terminate(&self) {
+        let idle = {
+            let mut idle = self.idle.lock().unwrap_or_else(|e| e.into_inner());
+            self.shutdown.store(true, Ordering::Release);
+            std::mem::take(&mut *idle)
+        };
+        idle.iter().for_each(|w| w.thread.unpark());
+    }
+}
+
+struct PoolShared {
+    registry: Arc<PoolRegistry>,
+    max_threads: usize,
+    thread_name: String,
+    stack_size: Option<usize>,
+    spawned: AtomicUsize,
+    thread_count: AtomicUsize
+}
+
+impl Drop for PoolShared {
+    fn drop(&mut self) {
+        self.registry.terminate();
+    }
+}
+
+/// ThreadPool is a handle to a set of reusable worker threads. Cloning the handle shares the same threads.
+/// The threads exit once the last handle is dropped.
+/// Upto max_threads threads are kept alive between jobs. If all of them are busy, for instance when
+/// parallel calls are nested or made concurrently, a transient thread is spawned for the job so that a call never
+/// waits on another one to free up a thread.
+/// ```
+/// use parallel_task::prelude::*;
+///
+/// let pool = ThreadPool::new(4);
+/// let res = (0..1_000).collect::<Vec<i32>>().parallel_iter().map(|val|val * 2).pool(&pool).collect::<Vec<i32>>();
+/// assert_eq!(res.len(),1_000);
+/// ```
+#[derive(Clone)]
+pub struct ThreadPool {
+    shared: Arc<PoolShared>
+}
+
+impl ThreadPool {
+
+    /// Creates a pool with upto max_threads threads. Use ThreadPoolBuilder for other settings.
+    pub fn new(max_threads:usize) -> Self {
+        ThreadPoolBuilder::new()
+        .num_threads(max_threads)
+        .build()
+    }
+
+    /// Global pool used by default by ParallelMap and ParallelForEach. It is initialised lazily on first use
+    /// unless configured earlier via ThreadPoolBuilder::build_global.
+    pub fn global() -> &'static ThreadPool {
+        GLOBAL_POOL.get_or_init(|| ThreadPoolBuilder::new().build())
+    }
+
+    /// Maximum number of threads kept alive by the pool
+    pub fn max_threads(&self) -> usize {
+        self.shared.max_threads
+    }
+
+    /// Prefix of the names given to the pool threads
+    pub fn thread_name(&self) -> &str {
+        &self.shared.thread_name
+    }
+
+    /// Stack size of the pool threads if set
+    pub fn stack_size(&self) -> Option<usize> {
+        self.shared.stack_size
+    }
+
+    /// Number of threads currently kept alive by the pool
+    pub fn current_threads(&self) -> usize {
+        self.shared.spawned.load(Ordering::Acquire)
+    }
+
+    /// Creates a scope within which jobs borrowing non 'static data may be spawned on the pool threads.
+    /// All the jobs are awaited before the function returns.
+    /// ```
+    /// use parallel_task::prelude::*;
+    ///
+    /// let values = vec![1,2,3];
+    /// let sum = ThreadPool::global().scope(|s| {
+    ///     let handle = s.spawn(|| values.iter().sum::<i32>()).unwrap();
+    ///     handle.join().unwrap()
+    /// });
+    /// assert_eq!(sum,6);
+    /// ```
+    pub fn scope<'env, F, T>(&self, f:F) -> T
+    where F: for<'scope> FnOnce(&'scope PoolScope<'scope, 'env>) -> T
+    {
+        let scope = PoolScope {
+            pool: self.clone(),
+            pending: Arc::new(PendingJobs::default()),
+            scope: PhantomData,
+            env: PhantomData
+        };
+        let result = catch_unwind(AssertUnwindSafe(|| f(&scope)));
+        scope.pending.wait();
+        match result {
+            Ok(res) => res,
+            Err(e) => resume_unwind(e)
+        }
+    }
+
+    /// Hands over the job to an idle thread, waking it up. If there are no idle threads, a new thread is spawned.
+    fn execute(&self, job:Job) -> std::io::Result<Thread> {
+        let registry = &self.shared.registry;
+        if let Some(idle) = registry.pop_idle() {
+            *idle.worker.job.lock().unwrap_or_else(|e| e.into_inner()) = Some(job);
+            idle.thread.unpark();
+            return Ok(idle.thread);
+        }
+
+        let persistent = !registry.is_shutdown() && self.shared.spawned
+        .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| (n < self.shared.max_threads).then_some(n + 1))
+        .is_ok();
+        let worker = Arc::new(PoolWorker { job: Mutex::new(Some(job)) });
+        let thread_registry = registry.clone();
+        let thread_name = format!("{}{}",self.shared.thread_name,self.shared.thread_count.fetch_add(1, Ordering::Relaxed));
+        let mut builder = std::thread::Builder::new().name(thread_name);
+        if let Some(stack_size) = self.shared.stack_size {
+            builder = builder.stack_size(stack_size);
+        }
+
+        match builder
+        .spawn(move || Self::worker_loop(thread_registry, worker, persistent)) {
+            Ok(handle) => Ok(handle.thread().clone()),
+            Err(e) => {
+                if persistent {
+                    self.shared.spawned.fetch_sub(1, Ordering::AcqRel);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Loop run by each pool thread. The thread parks itself while there is no job. Transient threads exit
+    /// after the first job.
+    fn worker_loop(registry:Arc<PoolRegistry>, worker:Arc<PoolWorker>, persistent:bool) {
+        loop {
+            let job = worker.job.lock().unwrap_or_else(|e| e.into_inner()).take();
+            if let Some(job) = job {
+                job();
+                if !persistent || !registry.release(&worker) {
+                    break;
+                }
+            } else if registry.is_shutdown() {
+                break;
+            } else {
+                std::thread::park();
+            }
+        }
+    }
+}
+
+const DEFAULT_THREAD_NAME:&str = "T:";
+
+/// ThreadPoolBuilder configures and builds a ThreadPool.
+/// By default the number of threads is the CPUs available times the CPU to thread ratio (2 by default).
+/// The environment variable PARALLEL_TASK_NUM_THREADS caps the number of threads of every pool, including any
+/// number set explicitly, so that it may be limited without a code change.
+/// ```
+/// use parallel_task::prelude::*;
+///
+/// let pool = ThreadPoolBuilder::new()
+///     .num_threads(4)
+///     .thread_name("worker-")
+///     .stack_size(4 * 1024 * 1024)
+///     .build();
+/// assert!(pool.max_threads() <= 4);
+/// assert_eq!(pool.thread_name(),"worker-");
+/// ```
+#[derive(Debug,Clone,Default)]
+pub struct ThreadPoolBuilder {
+    num_threads: Option<usize>,
+    cpu_to_thread_ratio: Option<usize>,
+    thread_name: Option<String>,
+    stack_size: Option<usize>
+}
+
+impl ThreadPoolBuilder {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maximum number of threads kept by the pool. This takes precedence over the CPU to thread ratio.
+    pub fn num_threads(mut self, num_threads:usize) -> Self {
+        self.num_threads = Some(num_threads);
+        self
+    }
+
+    /// Number of threads per available CPU, used when the number of threads is not set.
+    pub fn cpu_to_thread_ratio(mut self, ratio:usize) -> Self {
+        self.cpu_to_thread_ratio = Some(ratio);
+        self
+    }
+
+    /// Prefix of the thread names. Each thread is named as the prefix followed by a running number.
+    pub fn thread_name<S:Into<String>>(mut self, prefix:S) -> Self {
+        self.thread_name = Some(prefix.into());
+        self
+    }
+
+    /// Stack size of the pool threads in bytes
+    pub fn stack_size(mut self, stack_size:usize) -> Self {
+        self.stack_size = Some(stack_size);
+        self
+    }
+
+    /// Maximum number of threads the pool would be built with
+    pub fn max_threads(&self) -> usize {
+        let num_threads = match self.num_threads {
+            Some(num_threads) => utils::cap_to_env_limit(num_threads),
+            None => utils::max_threads_for_ratio(self.cpu_to_thread_ratio.unwrap_or(utils::CPU_2_THREAD_RATIO))
+        };
+        usize::max(num_threads, 1)
+    }
+
+    pub fn build(self) -> ThreadPool {
+        ThreadPool {
+            shared: Arc::new(PoolShared {
+                registry: Arc::new(PoolRegistry {
+                    idle: Mutex::new(Vec::new()),
+                    shutdown: AtomicBool::new(false)
+                }),
+                max_threads: self.max_threads(),
+                thread_name: self.thread_name.unwrap_or_else(|| DEFAULT_THREAD_NAME.to_owned()),
+                stack_size: self.stack_size,
+                spawned: AtomicUsize::new(0),
+                thread_count: AtomicUsize::new(0)
+            })
+        }
+    }
+
+    /// Builds the pool and sets it as the global pool. This fails if the global pool has already been
+    /// initialised, either explicitly or by a parallel call.
+    pub fn build_global(self) -> Result<(), WorkThreadError> {
+        GLOBAL_POOL.set(self.build())
+        .map_err(|_| WorkThreadError::GlobalPoolInitialised)
+    }
+}
+
+/// Count of jobs spawned within a scope that are yet to complete
+#[derive(Default)]
+struct PendingJobs {
+    count: Mutex<usize>,
+    done: Condvar
+}
+
+impl PendingJobs {
+    fn add(&self) {
+        *self.count.lock().unwrap_or_else(|e| e.into_inner()) += 1;
+    }
+
+    fn remove(&self) {
+        let mut count = self.count.lock().unwrap_or_else(|e| e.into_inner());
+        *count -= 1;
+        if *count == 0 {
+            self.done.notify_all();
+        }
+    }
+
+    fn wait(&self) {
+        let mut count = self.count.lock().unwrap_or_else(|e| e.into_inner());
+        while *count > 0 {
+            count = self.done.wait(count).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+}
+
+/// PoolScope is the equivalent of std::thread::Scope for the ThreadPool. Jobs spawned within may borrow
+/// any data that outlives the scope.
+pub struct PoolScope<'scope, 'env: 'scope> {
+    pool: ThreadPool,
+    pending: Arc<PendingJobs>,
+    scope: PhantomData<&'scope mut &'scope ()>,
+    env: PhantomData<&'env mut &'env ()>
+}
+
+impl<'scope, 'env> PoolScope<'scope, 'env> {
+
+    /// Runs the function on a pool thread. Panics within the function are caught and returned on join.
+    pub fn spawn<F, T>(&'scope self, f:F) -> std::io::Result<PoolJoinHandle<'scope, T>>
+    where F: FnOnce() -> T + Send + 'scope,
+    T: Send + 'scope
+    {
+        let (sender, receiver) = sync_channel(1);
+        let pending = self.pending.clone();
+        pending.add();
+        let job: Box<dyn FnOnce() + Send + 'scope> = Box::new(move || {
+            let result = catch_unwind(AssertUnwindSafe(f));
+            _ = sender.send(result);
+            // Nothing borrowed from the scope may be touched once the job is marked as complete.
+            drop(sender);
+            pending.remove();
+        });
+        // SAFETY: PoolScope::scope does not return before every job spawned within it has run to completion,
+        // so the job cannot outlive the data it borrows even though its lifetime is erased here.
+        let job: Job = unsafe { std::mem::transmute::<Box<dyn FnOnce() + Send + 'scope>, Job>(job) };
+
+        match self.pool.execute(job) {
+            Ok(thread) => Ok(PoolJoinHandle { receiver, thread, scope: PhantomData }),
+            Err(e) => {
+                // The job was dropped without being run
+                self.pending.remove();
+                Err(e)
+            }
+        }
+    }
+}
+
+/// Handle to a job spawned within a PoolScope
+pub struct PoolJoinHandle<'scope, T> {
+    receiver: Receiver<std::thread::Result<T>>,
+    thread: Thread,
+    scope: PhantomData<&'scope ()>
+}
+
+impl<T> PoolJoinHandle<'_, T> {
+
+    /// Pool thread running the job
+    pub fn thread(&self) -> &Thread {
+        &self.thread
+    }
+
+    /// Waits for the job to complete. Returns the panic payload if the job panicked.
+    pub fn join(self) -> Result<T, Box<dyn Any + Send + 'static>> {
+        self.receiver.recv()
+        .unwrap_or_else(|_| Err(Box::new("pool thread exited without returning a result")))
+    }
+}
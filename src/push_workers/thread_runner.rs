@@ -1,50 +1,164 @@
 //! ThreadRunner is responsible for running the tasks within queue belonging to each thread. It manages the process within a run function that is effectively
-//! a loop. The queue itself is a LimitedAccessQueue with the Secondary Accessor being available here
+//! a loop. The queue itself is a LimitedAccessQueue with the Secondary Accessor being available here.
+//! The outputs are folded by the Reducer into an accumulator for each run of consecutive values from the source, which
+//! is returned along with the index the run starts at. The controller combines these in order once the runners are joined.
+//! When a halt check is set and an output satisfies it, the runner sets its state to Unwind and stops. The controller
+//! then tells the other runners to stop as well. An ordered halt instead lowers a bound shared by all the runners, past
+//! which values are skipped. A panic within the function is caught, the state is set to Panic
+//! and the payload is returned along with the index of the value being processed, so that the controller may stop the
+//! other runners and hand the panic back to the caller.
 
-use std::sync::{Arc, RwLock};
+use std::{any::Any, panic::{catch_unwind, AssertUnwindSafe}, sync::{atomic::{AtomicUsize, Ordering}, Arc}};
 
-use crate::{accessors::read_accessor::SecondaryAccessor, push_workers::worker_thread::Coordination, utils::SpinWait};
+use crate::{accessors::read_accessor::SecondaryAccessor, pipeline::Pipeline, push_workers::worker_thread::Coordination, reduce::Reducer, utils::SpinWait};
 
-pub struct ThreadRunner<F,V,T> 
+/// Panic caught within a runner along with the index of the value being processed
+pub struct RunnerPanic {
+    pub index: Option<usize>,
+    pub payload: Box<dyn Any + Send + 'static>
+}
+
+/// Halt check run on each output. Once an output satisfies it, either all runners are stopped or, if the halt is
+/// ordered, the values after the one giving the output are skipped by all runners.
+pub struct Halt<T> {
+    check: fn(&T) -> bool,
+    ordered: bool,
+    bound: Arc<AtomicUsize>
+}
+
+impl<T> Clone for Halt<T> {
+    fn clone(&self) -> Self {
+        Self { check: self.check, ordered: self.ordered, bound: self.bound.clone() }
+    }
+}
+
+impl<T> Halt<T> {
+    /// Stops all the runners once an output satisfies the check
+    pub fn all(check:fn(&T) -> bool) -> Self {
+        Self { check, ordered: false, bound: Arc::new(AtomicUsize::new(usize::MAX)) }
+    }
+
+    /// Skips the values after the earliest one giving an output that satisfies the check
+    pub fn ordered(check:fn(&T) -> bool) -> Self {
+        Self { check, ordered: true, bound: Arc::new(AtomicUsize::new(usize::MAX)) }
+    }
+
+    pub fn is_met(&self, output:&T) -> bool {
+        (self.check)(output)
+    }
+
+    pub fn is_ordered(&self) -> bool {
+        self.ordered
+    }
+
+    /// Lowers the bound to the index if it is earlier
+    pub fn set_bound(&self, idx:usize) {
+        self.bound.fetch_min(idx, Ordering::SeqCst);
+    }
+
+    /// Values after the bound need not be processed
+    pub fn is_past_bound(&self, idx:usize) -> bool {
+        idx > self.bound.load(Ordering::Relaxed)
+    }
+}
+
+/// Accumulators of a runner, each tagged with the index in the source its run starts at, or the panic that stopped it
+pub type RunnerResult<A> = Result<Vec<(usize,A)>,RunnerPanic>;
+
+pub struct ThreadRunner<F,V,T,R> 
 where T:Send,
 V:Send,
-F:Fn(V) -> T
+F:Pipeline<V,Output=T>,
+R:Reducer<T>
 {            
     pos:usize, 
-    f:Arc<RwLock<F>>,
-    secondary_q:SecondaryAccessor<V,Coordination>,    
+    f:Arc<F>,
+    reducer:Arc<R>,
+    secondary_q:SecondaryAccessor<(usize,V),Coordination>,    
+    halt:Option<Halt<T>>,
 }
 
-impl<F,V,T> ThreadRunner<F,V,T> 
+impl<F,V,T,R> ThreadRunner<F,V,T,R> 
 where T:Send,
 V:Send,
-F:Fn(V) -> T {
+F:Pipeline<V,Output=T>,
+R:Reducer<T> {
 
-    pub fn new(pos:usize, secondary_q:SecondaryAccessor<V,Coordination>, 
-        f:Arc<RwLock<F>>) -> Self 
+    pub fn new(pos:usize, secondary_q:SecondaryAccessor<(usize,V),Coordination>, 
+        f:Arc<F>, reducer:Arc<R>, halt:Option<Halt<T>>) -> Self 
     {
 
         Self {                                    
             pos,
             f,
-            secondary_q            
+            reducer,
+            secondary_q,
+            halt            
         }
 
     }    
 
-    fn process(&mut self, final_values:&mut Vec<T>) 
+    fn process(&mut self, state:&mut Option<F::State>, runs:&mut Vec<(usize,R::Acc)>, current:&mut Option<usize>) 
     {
-        let fread: std::sync::RwLockReadGuard<'_, F> = self.f.read().unwrap();
-        SpinWait::loop_while_mut(||self.secondary_q.is_empty());        
-        while let Some(value) = self.secondary_q.pop() {            
-            final_values.push(fread(value));                                              
+        let f = &*self.f;
+        let reducer = &*self.reducer;
+        let halt_check = self.halt.as_ref();
+        while let Some((idx,value)) = self.secondary_q.pop() {            
+            if halt_check.is_some_and(|halt| halt.is_past_bound(idx)) {
+                continue;
+            }
+            // The run carries on while the values follow on from each other in the source
+            let follows_on = current.is_some_and(|last| last + 1 == idx);
+            let (start, acc) = match runs.pop() {
+                Some(run) if follows_on => run,
+                run => {
+                    runs.extend(run);
+                    (idx, reducer.identity())
+                }
+            };
+            *current = Some(idx);
+            // The state is only created once the runner has a value for it, so idle runners never build it
+            let state = state.get_or_insert_with(|| f.init_state());
+            let mut acc = Some(acc);
+            // A value may give any number of outputs, all of which are folded in the order given
+            let proceed = f.feed(state, value, &mut |output| {
+                let halt = halt_check.is_some_and(|halt| halt.is_met(&output));
+                acc = acc.take().map(|acc| reducer.fold(acc, idx, output));
+                !halt
+            });
+            runs.extend(acc.map(|acc| (start, acc)));
+            if let Some(halt) = halt_check.filter(|halt| !proceed && halt.is_ordered()) {
+                // The remaining values in the queue come after this one and are skipped
+                halt.set_bound(idx);
+            } else if !proceed {
+                // Signal the controller to stop all runners. The values remaining in the queue are abandoned.
+                self.secondary_q.set_state(Coordination::Unwind);
+                return;
+            }
         }
-        self.secondary_q.set_state(Coordination::Waiting);                                                                                                                                                                                                          
+        // The controller may have asked the runner to unwind in the meantime, which must not be overwritten
+        _ = self.secondary_q.compare_exchange_state(Coordination::Run, Coordination::Waiting);                                                                                                                                                                                                          
     }
 
-    pub fn run(&mut self) -> Vec<T> {
-        let mut final_values:Vec<T> = Vec::new();                               
+    pub fn run(&mut self) -> RunnerResult<R::Acc> {
+        let mut runs:Vec<(usize,R::Acc)> = Vec::new();                               
+        let mut current:Option<usize> = None;
 
+        match catch_unwind(AssertUnwindSafe(|| {
+            // The state is created within the runner's thread, so a panic in an init closure is reported as any other
+            let mut state = None;
+            self.task_loop(&mut state, &mut runs, &mut current)
+        })) {
+            Ok(()) => Ok(runs),
+            Err(payload) => {
+                // The controller picks this up and asks the other runners to unwind
+                self.secondary_q.set_state(Coordination::Panic);
+                Err(RunnerPanic { index: current, payload })
+            }
+        }
+    }
+
+    fn task_loop(&mut self, state:&mut Option<F::State>, runs:&mut Vec<(usize,R::Acc)>, current:&mut Option<usize>) {
         loop 
         {                                    
             match self.secondary_q.state() {                
@@ -52,13 +166,13 @@ F:Fn(V) -> T {
                     std::thread::park();
                 },
                 Coordination::Run => {                                                             
-                    self.process(&mut final_values);                        
+                    self.process(state, runs, current);                        
                 },
                 Coordination::Done => {                      
                     break;
                 },
                 Coordination::Unwind => {
-                    panic!("There was some error.");
+                    break;
                 },
                 Coordination::Panic => {
                     panic!("There was some error.");
@@ -69,8 +183,6 @@ F:Fn(V) -> T {
                 _ => {}
             }            
         }
-
-        final_values       
     }
 
     pub fn pos(&self) -> usize {
@@ -2,13 +2,16 @@
 //! in an efficient manner. It follows a primary task distribution, followed by task redistribution across threads on the principle of
 //! stealing, followed by joining across threads to return. 
 
-use std::sync::{Arc, RwLock};
-use std::thread::Scope;
-use crate::collector::Collector;
+use std::sync::Arc;
+use crate::collector::CollectTarget;
 use crate::errors::WorkThreadError;
+use crate::pipeline::Pipeline;
 use crate::prelude::AtomicIterator;
 use crate::push_workers::priorisation::PrioritizeThread;
 use crate::push_workers::thread_manager::ThreadManager;
+use crate::push_workers::thread_pool::{PoolScope, ThreadPool};
+use crate::push_workers::thread_runner::Halt;
+use crate::reduce::{Collect, Reducer};
 
 use super::worker_thread::WorkerThread;
 
@@ -16,21 +19,27 @@ pub const INITIAL_WORKERS:usize = 1;
 const MIN_QUEUE_LENGTH:usize = 2;
 
 pub struct WorkerController<F,V,T,I,P> 
-where F: Fn(V) -> T + Send + Sync,
+where F: Pipeline<V,Output=T>,
 V: Send + Sync,
 T: Send + Sync,    
 I:AtomicIterator<AtomicItem = V> + Send + Sized,
 P: PrioritizeThread
 {
-    f:Arc<RwLock<F>>,
+    f:Arc<F>,
     values: I,  
+    next_index: usize,
     avg_task_len: Option<usize>,    
     max_threads: usize,
+    pool: ThreadPool,
+    halt: Option<Halt<T>>,
+    // Sources of unknown length, or chained sources, give their values over several pulls. The source is pulled
+    // from again for the free threads till it gives no more values.
+    pending_source: bool,
     priority_strategy: P
 }
 
 impl<F,V,T,I,P>  WorkerController<F,V,T,I,P>
-where F: Fn(V) -> T + Send + Sync,
+where F: Pipeline<V,Output=T>,
 V: Send + Sync,
 T: Send + Sync,    
 I:AtomicIterator<AtomicItem = V> + Send + Sized,
@@ -39,17 +48,46 @@ P: PrioritizeThread
 
     pub fn new(f:F, values:I, strategy: P) -> Self 
     {                      
+        // Sources without a known length are pulled from in batches until they are exhausted
+        let pending_source = values.is_pending();
         Self {
-            f: Arc::new(RwLock::new(f)),
+            f: Arc::new(f),
             values,            
+            next_index: 0,
             avg_task_len:None,
-            max_threads: crate::utils::max_threads(),
+            max_threads: ThreadPool::global().max_threads(),
+            pool: ThreadPool::global().clone(),
+            halt: None,
+            pending_source,
             priority_strategy: strategy
         }
     }
 
     pub fn set_max_threads(&mut self, limit:usize) {
-        self.max_threads = usize::min(limit, crate::utils::max_threads());
+        self.max_threads = usize::max(usize::min(limit, self.pool.max_threads()),1);
+    }
+
+    /// Sets the pool from which the worker threads are leased. The thread limit is reset to the pool size.
+    pub fn set_pool(&mut self, pool:&ThreadPool) {
+        self.pool = pool.clone();
+        self.max_threads = pool.max_threads();
+    }
+
+    /// Sets a check on each output. Once an output satisfies the check, all threads are stopped without processing
+    /// the values remaining in their queues. The outputs processed till then are collected.
+    pub fn set_halt(&mut self, halt:fn(&T) -> bool) {
+        self.set_halt_check(Halt::all(halt));
+    }
+
+    /// Sets a check on each output. Once an output satisfies the check, the values after the one giving the output
+    /// are skipped by all threads, while the values before it are still processed. This allows the first output
+    /// satisfying the check to be found without processing the whole source.
+    pub fn set_ordered_halt(&mut self, halt:fn(&T) -> bool) {
+        self.set_halt_check(Halt::ordered(halt));
+    }
+
+    pub fn set_halt_check(&mut self, halt:Halt<T>) {
+        self.halt = Some(halt);
     }
 
     pub fn set_priority_strategy(&mut self, strategy:P) {
@@ -64,7 +102,7 @@ P: PrioritizeThread
         self.avg_task_len = Some(size);
     }
 
-    fn add_next_task(&mut self, vec_tasks:&mut Vec<Vec<V>>) {
+    fn add_next_task(&mut self, vec_tasks:&mut Vec<Vec<(usize,V)>>) {
         if let Some(vec) = self.next_task() {
             vec_tasks.push(vec);
         }
@@ -72,13 +110,32 @@ P: PrioritizeThread
 
     ///run function is usually called after WorkerController is instantiated.
     ///It is responsible for running the three processes: generate threads and pull from primary queue, 
-    /// redistribute and conquer work amongst threads and join for closure
+    /// redistribute and conquer work amongst threads and join for closure. The outputs are collected in order.
     pub fn run<C>(&mut self) -> Result<C,WorkThreadError>
-    where C: Collector<T>,    
+    where C: CollectTarget<T>,    
     {                                             
-        std::thread::scope(            
-            |s: &Scope<'_, '_>| {                 
-                let mut thread_manager = ThreadManager::new(s,self.f.clone(), self.max_threads);                                                                                                                                                                                                                                                                                                                                                                                                                                                     
+        self.reduce(Collect::<C>::default()).map(C::finish)
+    }
+
+    /// Runs the reducer within the threads and combines the accumulators of all threads in the order of the source.
+    pub fn reduce<R>(&mut self, reducer:R) -> Result<R::Acc,WorkThreadError>
+    where R: Reducer<T>
+    {
+        let reducer = Arc::new(reducer);
+        let runs = self.run_reducer(reducer.clone())?;
+        Ok(runs.into_iter()
+        .reduce(|left, right| reducer.combine(left, right))
+        .unwrap_or_else(|| reducer.identity()))
+    }
+
+    /// Runs the reducer within the threads and returns the accumulators of all threads in the order of the source.
+    fn run_reducer<R>(&mut self, reducer:Arc<R>) -> Result<Vec<R::Acc>,WorkThreadError>
+    where R: Reducer<T>
+    {
+        let pool = self.pool.clone();
+        pool.scope(            
+            |s: &PoolScope<'_, '_>| {                 
+                let mut thread_manager = ThreadManager::new(s,self.f.clone(), reducer, self.max_threads, self.halt.clone());                                                                                                                                                                                                                                                                                                                                                                                                                                                     
                 let control_time = self.primary_queue_distribution(&mut thread_manager)?;                                        
                 self.redistribute_among_threads( &mut thread_manager,control_time);                                                                      
                 thread_manager.join_all_threads()                
@@ -86,15 +143,16 @@ P: PrioritizeThread
         )        
     }
 
-    fn primary_queue_distribution<'env, 'scope>(&mut self, thread_manager: &mut ThreadManager<'env, 'scope,V,T,F>) -> Result<u128,WorkThreadError>
+    fn primary_queue_distribution<'env, 'scope,R>(&mut self, thread_manager: &mut ThreadManager<'env, 'scope,V,T,F,R>) -> Result<u128,WorkThreadError>
     where 'env: 'scope,     
     V: Send + Sync + 'scope,
     T: Send + Sync + 'scope, 
-    F:Fn(V) -> T + Send + Sync + 'scope
+    F:Pipeline<V,Output=T> + 'scope,
+    R:Reducer<T> + 'scope
     {
 
         // Intermediate buffer to store the tasks
-        let mut vec_tasks:Vec<Vec<V>> = Vec::new();        
+        let mut vec_tasks:Vec<Vec<(usize,V)>> = Vec::new();        
 
         // Generate initial worker threads as you need at least 1 by default. Record control time
         let tm = std::time::Instant::now();                   
@@ -118,24 +176,35 @@ P: PrioritizeThread
     /// queue of tasks, then the former should get half to save on time.
     /// It does this till the thread with the biggest queue has upto or less than 10% of the tasks from the intial 
     /// chunkwise distribution in the primary loop. 
-    fn redistribute_among_threads<'env, 'scope>(&mut self, thread_manager: &mut ThreadManager<'env, 'scope,V,T,F>, mut control_time:u128) 
+    fn redistribute_among_threads<'env, 'scope,R>(&mut self, thread_manager: &mut ThreadManager<'env, 'scope,V,T,F,R>, mut control_time:u128) 
     where 'env: 'scope,     
     V: Send + Sync + 'scope,
     T: Send + Sync + 'scope,   
     F: Send + Sync + 'scope,   
+    R: Reducer<T> + 'scope,
     {        
         if thread_manager.thread_len() > 0 //if just 2 threads, there is nothing to redistribute as such
         && self.avg_task_length().is_some() //ensure at least one set of values was sent to queue
         {                                                                               
             let mut stop_loop = false;
             loop {                     
+                if thread_manager.check_halt() {
+                    break;
+                }
+
                 if let Ok(tm) = thread_manager.refresh_free_threads(control_time) {
                     control_time = tm;
                 }                
 
+                // Values still to be pulled from the source are sent to the free threads ahead of any stealing
+                if thread_manager.has_free_threads() && self.has_pending_values() {
+                    self.feed_free_threads(thread_manager);
+                    continue;
+                }
+
                 if thread_manager.has_free_threads() && !stop_loop {                                      
                     let vec_ranking = self.priority_strategy.prioritize(thread_manager);                                    
-                    let mut task:Option<Vec<V>>;                    
+                    let mut task:Option<Vec<(usize,V)>>;                    
                     let min_queue_length = MIN_QUEUE_LENGTH; // At 2 jobs, there is nothing much to distribute 
                     for (idx,(pos,remaining))  in vec_ranking.into_iter().enumerate() {                        
                         if remaining <= min_queue_length {
@@ -174,23 +243,65 @@ P: PrioritizeThread
         } 
     }    
 
-    fn next_task(&mut self) -> Option<Vec<V>> {
-        self.values.atomic_pull()
+    /// Tests whether the source may have values left to pull after the primary distribution. Once an ordered halt
+    /// has been met, the values after it need not be pulled.
+    fn has_pending_values(&self) -> bool {
+        self.pending_source
+        && self.values.is_active()
+        && !self.halt.as_ref().is_some_and(|halt| halt.is_past_bound(self.next_index))
+    }
+
+    /// Sends the next batch of values from the source to each free thread, till the source is exhausted
+    fn feed_free_threads<'env, 'scope,R>(&mut self, thread_manager: &mut ThreadManager<'env, 'scope,V,T,F,R>)
+    where 'env: 'scope,
+    V: Send + Sync + 'scope,
+    T: Send + Sync + 'scope,
+    F: Send + Sync + 'scope,
+    R: Reducer<T> + 'scope,
+    {
+        while let Some(freepos) = thread_manager.pop_from_free_queue() {
+            let task = self.next_task();
+            if task.is_none() {
+                self.pending_source = false;
+            }
+            let thread = thread_manager.get_mut_thread(freepos);
+            if self.send_task(thread, task).is_err() {
+                thread_manager.add_to_free_queue(freepos);
+                break;
+            }
+        }
+    }
+
+    /// Pulls the next set of values from the iterator and tags each value with its position in the source.
+    /// The index travels with the value through the thread queues and any redistribution, and is used
+    /// to restore the input order when the results are joined.
+    /// The values are queued in reverse as the runners pop from the back. Each runner thus processes its values
+    /// in the order of the source. Stealing takes from the other end, so a stolen half is the stretch of values the
+    /// runner would have processed last.
+    fn next_task(&mut self) -> Option<Vec<(usize,V)>> {
+        let start = self.next_index;
+        let values = self.values.atomic_pull()?;
+        self.next_index += values.len();
+        Some(values.into_iter()
+        .enumerate()
+        .map(|(idx,value)| (start + idx, value))
+        .rev()
+        .collect())
     }
 
     #[allow(clippy::needless_lifetimes)] //this calls incorrectly otherwise
-    fn send_leaked_task<'scope>(&mut self, thread:&mut WorkerThread<'scope, V,T>, values:Vec<V>) -> Result<(),WorkThreadError>
+    fn send_leaked_task<'scope,A>(&mut self, thread:&mut WorkerThread<'scope, V,A>, values:Vec<(usize,V)>) -> Result<(),WorkThreadError>
     where V: Send + Sync + 'scope,
-    T: Send + Sync + 'scope,    
+    A: Send + 'scope,    
     I:AtomicIterator<AtomicItem = V> + Send + Sized 
     {                              
         thread.run(values)                             
     }
     
 
-    fn send_task<'scope>(&mut self, thread:&mut WorkerThread<'scope, V,T>, task:Option<Vec<V>>) -> Result<(),WorkThreadError>
+    fn send_task<'scope,A>(&mut self, thread:&mut WorkerThread<'scope, V,A>, task:Option<Vec<(usize,V)>>) -> Result<(),WorkThreadError>
     where V: Send + Sync + 'scope,
-    T: Send + Sync + 'scope,    
+    A: Send + 'scope,    
     I:AtomicIterator<AtomicItem = V> + Send + Sized 
     {            
         if let Some(values) = task {
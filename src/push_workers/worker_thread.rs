@@ -1,9 +1,9 @@
 //! Individual worker thread that is spawned by the workercontroller and thereon managed by
 //! the thread manager
 
-use std::{any::Any, error::Error, sync::{Arc, RwLock}};
+use std::{error::Error, sync::Arc};
 
-use crate::{accessors::{limit_queue, read_accessor::{PrimaryAccessor, SecondaryAccessor}}, errors::WorkThreadError, push_workers::thread_runner::ThreadRunner, utils::SpinWait};
+use crate::{accessors::{limit_queue, read_accessor::{PrimaryAccessor, SecondaryAccessor}}, errors::WorkThreadError, pipeline::Pipeline, push_workers::{thread_pool::{PoolJoinHandle, PoolScope}, thread_runner::{Halt, RunnerResult, ThreadRunner}}, reduce::Reducer, utils::SpinWait};
 
 
 /// Coordination is used as a State variable by the Primary and Secondary Accessors to manage the 
@@ -24,6 +24,29 @@ pub enum Coordination
     Processed=8
 }
 
+impl From<Coordination> for u8 {
+    fn from(state:Coordination) -> Self {
+        state as u8
+    }
+}
+
+impl From<u8> for Coordination {
+    fn from(state:u8) -> Self {
+        match state {
+            0 => Coordination::Waiting,
+            1 => Coordination::Park,
+            2 => Coordination::Done,
+            3 => Coordination::Unwind,
+            4 => Coordination::Panic,
+            5 => Coordination::Ignore,
+            6 => Coordination::ProcessTime,
+            7 => Coordination::Run,
+            8 => Coordination::Processed,
+            _ => unreachable!("{} is not a Coordination state", state)
+        }
+    }
+}
+
 
 /// Manage specific stats about the tasks in operation to enable the scheduling algorithm
 /// to take specific decisions
@@ -86,35 +109,37 @@ impl QueueStats {
 /// Worker thread is launched by the thread manager based on the need discovered by the 
 /// scheduling algorithm within worker controller.
 #[allow(dead_code)]
-pub struct WorkerThread<'scope,V,T> 
+pub struct WorkerThread<'scope,V,A> 
 where V:Send
 {
-    pub thread:Option<std::thread::ScopedJoinHandle<'scope,Vec<T>>>,
+    pub thread:Option<PoolJoinHandle<'scope,RunnerResult<A>>>,
     pub name:String,    
     pos: usize,    
-    primary_q: PrimaryAccessor<V,Coordination>,
+    primary_q: PrimaryAccessor<(usize,V),Coordination>,
     queue_stats:  Option<QueueStats>
 }
 
-impl<'scope,V,T> WorkerThread<'scope,V,T> 
-where T:Send + Sync + 'scope,
+impl<'scope,V,A> WorkerThread<'scope,V,A> 
+where A:Send + 'scope,
 V:Send + Sync + 'scope
 {
 
-    pub fn launch<'env,'a,F>(scope: &'scope std::thread::Scope<'scope, 'env>,
-    pos:usize,  f:Arc<RwLock<F>>) -> Result<Self,Box<dyn Error>> 
+    pub fn launch<'env,'a,F,T,R>(scope: &'scope PoolScope<'scope, 'env>,
+    pos:usize,  f:Arc<F>, reducer:Arc<R>, halt:Option<Halt<T>>) -> Result<Self,Box<dyn Error>> 
     where 'env: 'scope,    
     V:Send + Sync + 'scope,
-    F:Fn(V) -> T + Send + Sync + 'scope
+    T:Send + 'scope,
+    F:Pipeline<V,Output=T> + 'scope,
+    R:Reducer<T,Acc=A> + 'scope
     {                
-        let thread_name = format!("T:{}",pos);                              
-        let (primary_q, secondary_q) = limit_queue::LimitAccessQueue::<V,Coordination>::new();
+        let (primary_q, secondary_q) = limit_queue::LimitAccessQueue::<(usize,V),Coordination>::new();
 
-        match std::thread::Builder
-        ::new()
-        .name(thread_name.clone())
-        .spawn_scoped(scope, move || Self::task_loop(pos, secondary_q, f)) {
+        match scope.spawn(move || Self::task_loop(pos, secondary_q, f, reducer, halt)) {
             Ok(scoped_thread) => {
+                // Worker is named after the pool thread it has been leased
+                let thread_name = scoped_thread.thread().name()
+                .map(str::to_owned)
+                .unwrap_or_else(|| format!("T:{}",pos));
                 let worker = WorkerThread {
                     name:thread_name, 
                     thread: Some(scoped_thread),                    
@@ -146,8 +171,32 @@ V:Send + Sync + 'scope
         self.primary_q.state() == Coordination::Waiting
     }
 
+    /// Unwind is set by the runner when an output satisfied the halt check, or by the controller
+    /// to stop the runner.
+    pub fn is_halted(&mut self) -> bool {
+        self.primary_q.state() == Coordination::Unwind
+    }
+
+    /// Panic is set by the runner when the function panicked
+    pub fn is_panicked(&mut self) -> bool {
+        self.primary_q.state() == Coordination::Panic
+    }
+
+    /// A thread is idle once there is nothing left in its queue and it is not running
+    pub fn is_idle(&mut self) -> bool {
+        self.primary_q.is_empty() && self.primary_q.state() != Coordination::Run
+    }
+
+    /// Abandons the values remaining in the queue and signals the runner to stop.
+    pub fn halt(&mut self) {
+        _ = self.primary_q.steal();
+        if !self.is_panicked() {
+            self.signal(Coordination::Unwind);
+        }
+    }
+
     /// Run function runs a new batch of tasks on the thread
-    pub fn run(&mut self, values:Vec<V>) -> Result<(), WorkThreadError> {
+    pub fn run(&mut self, values:Vec<(usize,V)>) -> Result<(), WorkThreadError> {
         if values.is_empty() {
             Err(WorkThreadError::Other("Values within task shared to queue were empty.".to_owned()))
         } else {
@@ -168,38 +217,48 @@ V:Send + Sync + 'scope
     }
 
     fn done(&mut self) {                
-        SpinWait::loop_while_mut(||!self.primary_q.is_empty() || (self.primary_q.state() != Coordination::Waiting));        
-        self.primary_q.set_state(Coordination::Done);
+        SpinWait::loop_while_mut(||!self.is_idle());        
+        // A runner that has been halted has already stopped
+        _ = self.primary_q.compare_exchange_state(Coordination::Waiting, Coordination::Done);
     }    
 
-    fn task_loop<F>(pos:usize, secondary_q:SecondaryAccessor<V,Coordination>, f:Arc<RwLock<F>>) -> Vec<T>
+    fn task_loop<F,T,R>(pos:usize, secondary_q:SecondaryAccessor<(usize,V),Coordination>, f:Arc<F>, reducer:Arc<R>, halt:Option<Halt<T>>) -> RunnerResult<A>
     where T:Send,
     V:Send,
-    F:Fn(V) -> T
+    F:Pipeline<V,Output=T>,
+    R:Reducer<T,Acc=A>
     {   
-        ThreadRunner::new(pos,secondary_q, f)
+        ThreadRunner::new(pos,secondary_q, f, reducer, halt)
         .run()
     }
 
-    pub fn join(mut self) -> Result<Vec<T>, Box<dyn Any + Send + 'static>> 
+    /// Waits for the runner to complete and returns its accumulators. A panic within the runner is returned
+    /// as WorkThreadError::WorkerPanic carrying the original payload.
+    pub fn join(mut self) -> Result<Vec<(usize,A)>, WorkThreadError> 
     where V:Send + Sync + 'scope,
     {        
         self.done();     
-        self.thread.unwrap().join()                
+        let name = std::mem::take(&mut self.name);
+        match self.thread.take().map(|thread| thread.join()) {
+            Some(Ok(Ok(values))) => Ok(values),
+            Some(Ok(Err(panic))) => Err(WorkThreadError::WorkerPanic { worker: name, index: panic.index, payload: panic.payload }),
+            Some(Err(payload)) => Err(WorkThreadError::WorkerPanic { worker: name, index: None, payload }),
+            None => Err(WorkThreadError::ThreadJoin)
+        }
     }
 
     pub fn queue_len(&self) -> usize {
         self.primary_q.len()
     } 
 
-    pub fn steal(&mut self) -> Option<Vec<V>> {        
+    pub fn steal(&mut self) -> Option<Vec<(usize,V)>> {        
         let res = self.primary_q.steal();
         println!("[[{}:{:?}]]",self.primary_q.len(),res.as_ref().map(|q|q.len()));
         self.queue_stats = Some(QueueStats::new(self.primary_q.len(), std::time::Instant::now()));
         res
     }  
 
-    pub fn steal_half(&mut self) -> Option<Vec<V>> {        
+    pub fn steal_half(&mut self) -> Option<Vec<(usize,V)>> {        
         let res = self.primary_q.steal_half();         
         self.queue_stats = Some(QueueStats::new(self.primary_q.len(), std::time::Instant::now()));
         res
@@ -0,0 +1,376 @@
+//! Reductions run within the worker threads. Each ThreadRunner folds its outputs into an accumulator, starting a new one
+//! whenever the values it pops stop following on from each other in the source. The accumulators are combined in the
+//! order of the source once all threads are joined, so the outputs are never gathered into an intermediate collection.
+//! As the combining keeps the order, the operations need to be associative but not commutative.
+
+use std::iter::{Product, Sum};
+use std::marker::PhantomData;
+use crate::collector::CollectTarget;
+use crate::map::ParallelMap;
+use crate::pipeline::{Identity, Pipeline};
+use crate::worker_thread::WorkerThreads;
+use super::iterators::iterator::*;
+
+/// Reducer is run by the ThreadRunner on every output of the pipeline.
+pub trait Reducer<T>: Send + Sync {
+    type Acc: Send;
+
+    /// Starting value of every accumulator
+    fn identity(&self) -> Self::Acc;
+
+    /// Adds an output to the accumulator, along with the index in the source of the value that gave it
+    fn fold(&self, acc:Self::Acc, index:usize, value:T) -> Self::Acc;
+
+    /// Combines two accumulators, where the left one holds the outputs that come first in the source
+    fn combine(&self, left:Self::Acc, right:Self::Acc) -> Self::Acc;
+}
+
+/// Gathers the outputs in a partial collection for each run, which are merged in order.
+pub struct Collect<C> {
+    c: PhantomData<fn() -> C>
+}
+
+impl<C> Default for Collect<C> {
+    fn default() -> Self {
+        Self { c: PhantomData }
+    }
+}
+
+impl<T,C> Reducer<T> for Collect<C>
+where C: CollectTarget<T>
+{
+    type Acc = C::Partial;
+
+    fn identity(&self) -> Self::Acc {
+        C::partial()
+    }
+
+    fn fold(&self, mut acc:Self::Acc, _:usize, value:T) -> Self::Acc {
+        C::push(&mut acc, value);
+        acc
+    }
+
+    fn combine(&self, left:Self::Acc, right:Self::Acc) -> Self::Acc {
+        C::merge(left, right)
+    }
+}
+
+/// Reduces the outputs with an associative operation
+pub struct ReduceWith<ID,OP> {
+    identity: ID,
+    op: OP
+}
+
+impl<T,ID,OP> Reducer<T> for ReduceWith<ID,OP>
+where T: Send,
+ID: Fn() -> T + Send + Sync,
+OP: Fn(T,T) -> T + Send + Sync
+{
+    type Acc = T;
+
+    fn identity(&self) -> Self::Acc {
+        (self.identity)()
+    }
+
+    fn fold(&self, acc:Self::Acc, _:usize, value:T) -> Self::Acc {
+        (self.op)(acc, value)
+    }
+
+    fn combine(&self, left:Self::Acc, right:Self::Acc) -> Self::Acc {
+        (self.op)(left, right)
+    }
+}
+
+/// Folds the outputs into accumulators of another type, which are then combined
+pub struct FoldWith<ID,F,C> {
+    identity: ID,
+    fold: F,
+    combine: C
+}
+
+impl<T,A,ID,F,C> Reducer<T> for FoldWith<ID,F,C>
+where A: Send,
+ID: Fn() -> A + Send + Sync,
+F: Fn(A,T) -> A + Send + Sync,
+C: Fn(A,A) -> A + Send + Sync
+{
+    type Acc = A;
+
+    fn identity(&self) -> Self::Acc {
+        (self.identity)()
+    }
+
+    fn fold(&self, acc:Self::Acc, _:usize, value:T) -> Self::Acc {
+        (self.fold)(acc, value)
+    }
+
+    fn combine(&self, left:Self::Acc, right:Self::Acc) -> Self::Acc {
+        (self.combine)(left, right)
+    }
+}
+
+/// Adds up the outputs in the same way as Iterator::sum
+pub struct SumOf<S>(PhantomData<fn() -> S>);
+
+impl<T,S> Reducer<T> for SumOf<S>
+where S: Sum<T> + Sum<S> + Send
+{
+    type Acc = S;
+
+    fn identity(&self) -> Self::Acc {
+        std::iter::empty::<T>().sum()
+    }
+
+    fn fold(&self, acc:Self::Acc, _:usize, value:T) -> Self::Acc {
+        [acc, std::iter::once(value).sum()].into_iter().sum()
+    }
+
+    fn combine(&self, left:Self::Acc, right:Self::Acc) -> Self::Acc {
+        [left, right].into_iter().sum()
+    }
+}
+
+/// Multiplies the outputs in the same way as Iterator::product
+pub struct ProductOf<P>(PhantomData<fn() -> P>);
+
+impl<T,P> Reducer<T> for ProductOf<P>
+where P: Product<T> + Product<P> + Send
+{
+    type Acc = P;
+
+    fn identity(&self) -> Self::Acc {
+        std::iter::empty::<T>().product()
+    }
+
+    fn fold(&self, acc:Self::Acc, _:usize, value:T) -> Self::Acc {
+        [acc, std::iter::once(value).product()].into_iter().product()
+    }
+
+    fn combine(&self, left:Self::Acc, right:Self::Acc) -> Self::Acc {
+        [left, right].into_iter().product()
+    }
+}
+
+/// Keeps a single output, which is replaced whenever replace(kept, later) is true. This gives min, max and their
+/// variants, with ties settled in the same way as the Iterator functions.
+pub struct Pick<F> {
+    replace: F
+}
+
+impl<T,F> Reducer<T> for Pick<F>
+where T: Send,
+F: Fn(&T,&T) -> bool + Send + Sync
+{
+    type Acc = Option<T>;
+
+    fn identity(&self) -> Self::Acc {
+        None
+    }
+
+    fn fold(&self, acc:Self::Acc, _:usize, value:T) -> Self::Acc {
+        self.combine(acc, Some(value))
+    }
+
+    fn combine(&self, left:Self::Acc, right:Self::Acc) -> Self::Acc {
+        match (left, right) {
+            (Some(left), Some(right)) => if (self.replace)(&left, &right) { Some(right) } else { Some(left) },
+            (left, None) => left,
+            (None, right) => right
+        }
+    }
+}
+
+/// Counts the outputs
+pub struct Count;
+
+impl<T> Reducer<T> for Count {
+    type Acc = usize;
+
+    fn identity(&self) -> Self::Acc {
+        0
+    }
+
+    fn fold(&self, acc:Self::Acc, _:usize, _:T) -> Self::Acc {
+        acc + 1
+    }
+
+    fn combine(&self, left:Self::Acc, right:Self::Acc) -> Self::Acc {
+        left + right
+    }
+}
+
+/// Reductions on ParallelMap. The pipeline and the reduction are run together within the worker threads.
+/// ```
+/// use parallel_task::prelude::*;
+///
+/// let res = (0..100_000).collect::<Vec<u64>>().parallel_iter().map(|val| val * 2).sum::<u64>();
+/// assert_eq!(res,(0..100_000).map(|val| val * 2).sum::<u64>())
+/// ```
+///
+impl<I,V,F,T> ParallelMap<V,F,T,I>
+where I:AtomicIterator<AtomicItem = V> + Send + Sized,
+F: Pipeline<V,Output=T>,
+V: Send + Sync,
+T:Send + Sync
+{
+    /// Run a reducer on the outputs and combine the accumulators of all the threads
+    pub fn reduce_with<R>(self, reducer:R) -> R::Acc
+    where R: Reducer<T>
+    {
+        WorkerThreads { nthreads: self.num_threads, pool: self.pool.clone() }
+        .reduce(self, reducer, None)
+    }
+
+    /// Reduce the outputs with an associative operation. The identity is used to start every accumulator, so it may be
+    /// called several times and must leave the result unchanged when combined. It is returned when there are no outputs.
+    /// ```
+    /// use parallel_task::prelude::*;
+    ///
+    /// let res = vec!["a","b","c"].parallel_iter().map(|val| val.to_string()).reduce(String::new,|a,b| a + &b);
+    /// assert_eq!(res,"abc")
+    /// ```
+    ///
+    pub fn reduce<ID,OP>(self, identity:ID, op:OP) -> T
+    where ID: Fn() -> T + Send + Sync,
+    OP: Fn(T,T) -> T + Send + Sync
+    {
+        self.reduce_with(ReduceWith { identity, op })
+    }
+
+    /// Fold the outputs into accumulators of another type, starting each from identity, and combine the accumulators
+    /// of all the threads.
+    /// ```
+    /// use parallel_task::prelude::*;
+    ///
+    /// let res = (0..1_000).collect::<Vec<i32>>().parallel_iter().map(|val| *val)
+    /// .fold(|| 0usize,|acc,val| acc + val.to_string().len(),|a,b| a + b);
+    /// assert_eq!(res,(0..1_000).map(|val:i32| val.to_string().len()).sum::<usize>())
+    /// ```
+    ///
+    pub fn fold<A,ID,G,C>(self, identity:ID, fold:G, combine:C) -> A
+    where A: Send,
+    ID: Fn() -> A + Send + Sync,
+    G: Fn(A,T) -> A + Send + Sync,
+    C: Fn(A,A) -> A + Send + Sync
+    {
+        self.reduce_with(FoldWith { identity, fold, combine })
+    }
+
+    /// Sum the outputs
+    pub fn sum<S>(self) -> S
+    where S: Sum<T> + Sum<S> + Send
+    {
+        self.reduce_with(SumOf(PhantomData))
+    }
+
+    /// Multiply the outputs
+    pub fn product<P>(self) -> P
+    where P: Product<T> + Product<P> + Send
+    {
+        self.reduce_with(ProductOf(PhantomData))
+    }
+
+    /// The minimum output. The first one is returned if several are equally minimum.
+    pub fn min(self) -> Option<T>
+    where T: Ord
+    {
+        self.reduce_with(Pick { replace: |kept:&T, later:&T| later < kept })
+    }
+
+    /// The maximum output. The last one is returned if several are equally maximum.
+    pub fn max(self) -> Option<T>
+    where T: Ord
+    {
+        self.reduce_with(Pick { replace: |kept:&T, later:&T| later >= kept })
+    }
+
+    /// The output with the minimum key. The first one is returned if several are equally minimum.
+    pub fn min_by_key<K,G>(self, f:G) -> Option<T>
+    where K: Ord,
+    G: Fn(&T) -> K + Send + Sync
+    {
+        self.reduce_with(Pick { replace: move |kept:&T, later:&T| f(later) < f(kept) })
+    }
+
+    /// The output with the maximum key. The last one is returned if several are equally maximum.
+    pub fn max_by_key<K,G>(self, f:G) -> Option<T>
+    where K: Ord,
+    G: Fn(&T) -> K + Send + Sync
+    {
+        self.reduce_with(Pick { replace: move |kept:&T, later:&T| f(later) >= f(kept) })
+    }
+
+    /// Count the outputs
+    pub fn count(self) -> usize {
+        self.reduce_with(Count)
+    }
+}
+
+/// ParallelReduceIter allows the reductions to be called directly on type implementing AtomicIterator.
+/// ```
+/// use parallel_task::prelude::*;
+///
+/// let vec = (0..100_000).collect::<Vec<i64>>();
+/// assert_eq!(vec.parallel_iter().sum::<i64>(),vec.iter().sum::<i64>());
+/// assert_eq!(vec.parallel_iter().max(),vec.iter().max());
+/// assert_eq!(vec.parallel_iter().count(),100_000);
+/// ```
+///
+pub trait ParallelReduceIter<V>
+where Self: AtomicIterator<AtomicItem = V> + Send + Sized,
+V: Send + Sync
+{
+    fn reduce<ID,OP>(self, identity:ID, op:OP) -> V
+    where ID: Fn() -> V + Send + Sync,
+    OP: Fn(V,V) -> V + Send + Sync {
+        ParallelMap::new(self, Identity::default()).reduce(identity, op)
+    }
+
+    fn fold<A,ID,G,C>(self, identity:ID, fold:G, combine:C) -> A
+    where A: Send,
+    ID: Fn() -> A + Send + Sync,
+    G: Fn(A,V) -> A + Send + Sync,
+    C: Fn(A,A) -> A + Send + Sync {
+        ParallelMap::new(self, Identity::default()).fold(identity, fold, combine)
+    }
+
+    fn sum<S>(self) -> S
+    where S: Sum<V> + Sum<S> + Send {
+        ParallelMap::new(self, Identity::default()).sum()
+    }
+
+    fn product<P>(self) -> P
+    where P: Product<V> + Product<P> + Send {
+        ParallelMap::new(self, Identity::default()).product()
+    }
+
+    fn min(self) -> Option<V>
+    where V: Ord {
+        ParallelMap::new(self, Identity::default()).min()
+    }
+
+    fn max(self) -> Option<V>
+    where V: Ord {
+        ParallelMap::new(self, Identity::default()).max()
+    }
+
+    fn min_by_key<K,G>(self, f:G) -> Option<V>
+    where K: Ord,
+    G: Fn(&V) -> K + Send + Sync {
+        ParallelMap::new(self, Identity::default()).min_by_key(f)
+    }
+
+    fn max_by_key<K,G>(self, f:G) -> Option<V>
+    where K: Ord,
+    G: Fn(&V) -> K + Send + Sync {
+        ParallelMap::new(self, Identity::default()).max_by_key(f)
+    }
+
+    fn count(self) -> usize {
+        ParallelMap::new(self, Identity::default()).count()
+    }
+}
+
+impl<I,V> ParallelReduceIter<V> for I
+where I: AtomicIterator<AtomicItem = V> + Send + Sized,
+V: Send + Sync {}
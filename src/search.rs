@@ -0,0 +1,193 @@
+//! Short-circuiting searches. The outputs are checked within the worker threads, and once an answer is found the threads
+//! stop without processing the values remaining in their queues. For find_first and position_any only the values after
+//! the match are skipped, since an earlier match may still be found by another thread, and position_any needs the
+//! outputs before the match to be counted.
+
+use crate::map::ParallelMap;
+use crate::pipeline::{Identity, Pipeline};
+use crate::push_workers::thread_runner::Halt;
+use crate::reduce::Reducer;
+use crate::worker_thread::WorkerThreads;
+use super::iterators::iterator::*;
+
+/// True if any of the outputs is true
+pub struct AnyOf;
+
+impl Reducer<bool> for AnyOf {
+    type Acc = bool;
+
+    fn identity(&self) -> Self::Acc {
+        false
+    }
+
+    fn fold(&self, acc:Self::Acc, _:usize, value:bool) -> Self::Acc {
+        acc || value
+    }
+
+    fn combine(&self, left:Self::Acc, right:Self::Acc) -> Self::Acc {
+        left || right
+    }
+}
+
+/// True if all of the outputs are true
+pub struct AllOf;
+
+impl Reducer<bool> for AllOf {
+    type Acc = bool;
+
+    fn identity(&self) -> Self::Acc {
+        true
+    }
+
+    fn fold(&self, acc:Self::Acc, _:usize, value:bool) -> Self::Acc {
+        acc && value
+    }
+
+    fn combine(&self, left:Self::Acc, right:Self::Acc) -> Self::Acc {
+        left && right
+    }
+}
+
+/// Keeps the first output in the order of the source
+pub struct First;
+
+impl<T: Send> Reducer<T> for First {
+    type Acc = Option<T>;
+
+    fn identity(&self) -> Self::Acc {
+        None
+    }
+
+    fn fold(&self, acc:Self::Acc, _:usize, value:T) -> Self::Acc {
+        acc.or(Some(value))
+    }
+
+    fn combine(&self, left:Self::Acc, right:Self::Acc) -> Self::Acc {
+        left.or(right)
+    }
+}
+
+/// Counts the outputs and keeps the position among them of the first true output. The positions within a run are
+/// offset by the outputs of the runs before it when they are combined, so that adapters giving any number of outputs
+/// per value are counted correctly.
+pub struct Position;
+
+impl Reducer<bool> for Position {
+    type Acc = (usize,Option<usize>);
+
+    fn identity(&self) -> Self::Acc {
+        (0, None)
+    }
+
+    fn fold(&self, (count, found):Self::Acc, _:usize, value:bool) -> Self::Acc {
+        (count + 1, found.or(value.then_some(count)))
+    }
+
+    fn combine(&self, (left_count, left_found):Self::Acc, (right_count, right_found):Self::Acc) -> Self::Acc {
+        (left_count + right_count, left_found.or(right_found.map(|pos| left_count + pos)))
+    }
+}
+
+/// Searches on ParallelMap. The threads stop as soon as an answer is found.
+/// ```
+/// use parallel_task::prelude::*;
+///
+/// let vec = (0..1_000_000).collect::<Vec<u64>>();
+/// assert!(vec.parallel_iter().map(|val| val * 2).any(|val| val == 1_000));
+/// assert_eq!(vec.parallel_iter().map(|val| val * 2).find_first(|val| val % 7 == 6),Some(6));
+/// ```
+///
+impl<I,V,F,T> ParallelMap<V,F,T,I>
+where I:AtomicIterator<AtomicItem = V> + Send + Sized,
+F: Pipeline<V,Output=T>,
+V: Send + Sync,
+T:Send + Sync
+{
+    /// True if the predicate is true for any output. The threads stop once one is found.
+    pub fn any<P>(self, predicate:P) -> bool
+    where P: Fn(T) -> bool + Send + Sync
+    {
+        let workers = WorkerThreads { nthreads: self.num_threads, pool: self.pool.clone() };
+        workers.reduce(self.map(predicate), AnyOf, Some(Halt::all(|found:&bool| *found)))
+    }
+
+    /// True if the predicate is true for all the outputs. The threads stop once one is found for which it is false.
+    pub fn all<P>(self, predicate:P) -> bool
+    where P: Fn(T) -> bool + Send + Sync
+    {
+        let workers = WorkerThreads { nthreads: self.num_threads, pool: self.pool.clone() };
+        workers.reduce(self.map(predicate), AllOf, Some(Halt::all(|holds:&bool| !*holds)))
+    }
+
+    /// An output for which the predicate is true, which need not be the first one. The threads stop once one is found.
+    pub fn find_any<P>(self, predicate:P) -> Option<T>
+    where P: Fn(&T) -> bool + Send + Sync
+    {
+        let workers = WorkerThreads { nthreads: self.num_threads, pool: self.pool.clone() };
+        workers.reduce(self.filter(predicate), First, Some(Halt::all(|_:&T| true)))
+    }
+
+    /// The first output in the order of the source for which the predicate is true. Once one is found, the values after
+    /// it are skipped by all the threads.
+    pub fn find_first<P>(self, predicate:P) -> Option<T>
+    where P: Fn(&T) -> bool + Send + Sync
+    {
+        let workers = WorkerThreads { nthreads: self.num_threads, pool: self.pool.clone() };
+        workers.reduce(self.filter(predicate), First, Some(Halt::ordered(|_:&T| true)))
+    }
+
+    /// The position among the outputs of one for which the predicate is true, as with `Iterator::position`. The outputs
+    /// before it must all be counted, so this is the first such output. Once one is found, the values after it are
+    /// skipped by all the threads.
+    pub fn position_any<P>(self, predicate:P) -> Option<usize>
+    where P: Fn(T) -> bool + Send + Sync
+    {
+        let workers = WorkerThreads { nthreads: self.num_threads, pool: self.pool.clone() };
+        workers.reduce(self.map(predicate), Position, Some(Halt::ordered(|found:&bool| *found))).1
+    }
+}
+
+/// ParallelSearchIter allows the searches to be called directly on type implementing AtomicIterator.
+/// ```
+/// use parallel_task::prelude::*;
+///
+/// let vec = (0..1_000_000).collect::<Vec<u64>>();
+/// assert!(vec.parallel_iter().any(|val| *val == 999_999));
+/// assert!(!vec.parallel_iter().all(|val| *val < 10));
+/// assert_eq!(vec.parallel_iter().find_first(|val| **val > 10),Some(&11));
+/// assert_eq!(vec.parallel_iter().position_any(|val| *val == 500),Some(500));
+/// ```
+///
+pub trait ParallelSearchIter<V>
+where Self: AtomicIterator<AtomicItem = V> + Send + Sized,
+V: Send + Sync
+{
+    fn any<P>(self, predicate:P) -> bool
+    where P: Fn(V) -> bool + Send + Sync {
+        ParallelMap::new(self, Identity::default()).any(predicate)
+    }
+
+    fn all<P>(self, predicate:P) -> bool
+    where P: Fn(V) -> bool + Send + Sync {
+        ParallelMap::new(self, Identity::default()).all(predicate)
+    }
+
+    fn find_any<P>(self, predicate:P) -> Option<V>
+    where P: Fn(&V) -> bool + Send + Sync {
+        ParallelMap::new(self, Identity::default()).find_any(predicate)
+    }
+
+    fn find_first<P>(self, predicate:P) -> Option<V>
+    where P: Fn(&V) -> bool + Send + Sync {
+        ParallelMap::new(self, Identity::default()).find_first(predicate)
+    }
+
+    fn position_any<P>(self, predicate:P) -> Option<usize>
+    where P: Fn(V) -> bool + Send + Sync {
+        ParallelMap::new(self, Identity::default()).position_any(predicate)
+    }
+}
+
+impl<I,V> ParallelSearchIter<V> for I
+where I: AtomicIterator<AtomicItem = V> + Send + Sized,
+V: Send + Sync {}
@@ -0,0 +1,93 @@
+//! ParallelTryMap allows a fallible function to be run on the values of an AtomicIterator. The earliest Err in the order
+//! of the source is returned to the caller, instead of panicking within a worker thread. Once an Err is found the values
+//! after it are skipped by all the threads, while the values before it are still processed.
+
+use super::iterators::iterator::*;
+use crate::map::ParallelMap;
+use crate::push_workers::thread_pool::ThreadPool;
+use super::collector::*;
+
+/// ParallelTryMapIter allows calling the .try_map(f) on type implementing AtomicIterator to get a ParallelTryMap object 
+/// on which collect may be called.
+/// ```
+/// use parallel_task::prelude::*;
+/// 
+/// let res = (0..100_000).map(|val|val.to_string()).collect::<Vec<String>>()
+/// .parallel_iter()
+/// .try_map(|val| val.parse::<i32>())
+/// .collect::<Vec<i32>>();
+/// assert_eq!(res.unwrap().len(),100_000)
+/// ```
+/// 
+pub trait ParallelTryMapIter<I, V,F,T,E>
+where Self: AtomicIterator<AtomicItem = V> + Send + Sized,
+F: Fn(V) -> Result<T,E> + Send + Sync,
+V: Send + Sync,
+T:Send + Sync,
+E:Send + Sync
+{
+    fn try_map(self,f:F) -> ParallelTryMap<V,F,T,E,Self>{
+        ParallelTryMap::new(self,f)
+    }
+}
+
+impl<I,V,F,T,E> ParallelTryMapIter<I, V,F,T,E> for I 
+where I: AtomicIterator<AtomicItem = V> + Send + Sized,
+F: Fn(V) -> Result<T,E> + Send + Sync,
+V: Send + Sync,
+T:Send + Sync,
+E:Send + Sync {}
+
+/// ParallelTryMap is a object that allows a fallible map function to be run on AtomicIterators.
+/// The results may be then collected in types implementing Collector trait, or the first error is returned.
+/// 
+/// ```
+/// use parallel_task::prelude::*;
+/// 
+/// let res = vec!["1","2","x","4"].parallel_iter().try_map(|val| val.parse::<i32>()).collect::<Vec<i32>>();
+/// assert!(res.is_err())
+/// ```
+/// 
+pub struct ParallelTryMap<V,F,T,E,I>
+where I: AtomicIterator<AtomicItem = V> + Send + Sized,
+F: Fn(V) -> Result<T,E> + Send + Sync,
+V: Send + Sync,
+T:Send + Sync,
+E:Send + Sync
+{
+    pub map: ParallelMap<V,F,Result<T,E>,I>
+}
+
+impl<I,V,F,T,E> ParallelTryMap<V,F,T,E,I>
+where I:AtomicIterator<AtomicItem = V> + Send + Sized,
+F: Fn(V) -> Result<T,E> + Send + Sync,
+V: Send + Sync,
+T:Send + Sync,
+E:Send + Sync
+{
+    pub fn new(iter:I,f:F) -> Self
+    {                   
+        Self {
+            map: ParallelMap::new(iter, f)
+        }
+    }
+
+    /// Set the maximum number of threads used for running the parallel tasker. This is capped to the size of the pool.
+    pub fn threads(mut self, nthreads:usize) -> Self {
+        self.map = self.map.threads(nthreads);
+        self
+    }
+
+    /// Run the tasks on the given pool instead of the global pool.
+    pub fn pool(mut self, pool:&ThreadPool) -> Self {
+        self.map = self.map.pool(pool);
+        self
+    }
+
+    /// Collect the results in a type implementing Collector trait, or return the first error encountered.
+    pub fn collect<C>(self) -> Result<C,E>
+    where C: CollectTarget<T>
+    {
+        self.map.try_collect()
+    }
+}
@@ -0,0 +1,175 @@
+//! Unzip and partition split the outputs into two collections within the worker threads. Each thread fills its own
+//! pair of partial collections, which are merged in the order of the source once the threads are joined.
+
+use std::marker::PhantomData;
+use crate::collector::CollectTarget;
+use crate::map::ParallelMap;
+use crate::pipeline::{Identity, Pipeline};
+use crate::reduce::Reducer;
+use super::iterators::iterator::*;
+
+/// Gathers the two parts of the paired outputs in separate collections
+pub struct Unzip<A,B> {
+    ab: PhantomData<fn() -> (A,B)>
+}
+
+impl<X,Y,A,B> Reducer<(X,Y)> for Unzip<A,B>
+where A: CollectTarget<X>,
+B: CollectTarget<Y>
+{
+    type Acc = (A::Partial,B::Partial);
+
+    fn identity(&self) -> Self::Acc {
+        (A::partial(), B::partial())
+    }
+
+    fn fold(&self, mut acc:Self::Acc, _:usize, value:(X,Y)) -> Self::Acc {
+        A::push(&mut acc.0, value.0);
+        B::push(&mut acc.1, value.1);
+        acc
+    }
+
+    fn combine(&self, left:Self::Acc, right:Self::Acc) -> Self::Acc {
+        (A::merge(left.0, right.0), B::merge(left.1, right.1))
+    }
+}
+
+/// Gathers the outputs satisfying the predicate in the first collection and the others in the second
+pub struct Partition<A,B,P> {
+    predicate: P,
+    ab: PhantomData<fn() -> (A,B)>
+}
+
+impl<T,A,B,P> Reducer<T> for Partition<A,B,P>
+where A: CollectTarget<T>,
+B: CollectTarget<T>,
+P: Fn(&T) -> bool + Send + Sync
+{
+    type Acc = (A::Partial,B::Partial);
+
+    fn identity(&self) -> Self::Acc {
+        (A::partial(), B::partial())
+    }
+
+    fn fold(&self, mut acc:Self::Acc, _:usize, value:T) -> Self::Acc {
+        if (self.predicate)(&value) {
+            A::push(&mut acc.0, value);
+        } else {
+            B::push(&mut acc.1, value);
+        }
+        acc
+    }
+
+    fn combine(&self, left:Self::Acc, right:Self::Acc) -> Self::Acc {
+        (A::merge(left.0, right.0), B::merge(left.1, right.1))
+    }
+}
+
+/// Partition on ParallelMap. The outputs keep the order of the source within each collection.
+/// ```
+/// use parallel_task::prelude::*;
+///
+/// let (even, odd):(Vec<i32>,Vec<i32>) = (0..10).collect::<Vec<i32>>().parallel_iter().map(|val| *val).partition(|val| val % 2 == 0);
+/// assert_eq!(even,vec![0,2,4,6,8]);
+/// assert_eq!(odd,vec![1,3,5,7,9])
+/// ```
+///
+impl<I,V,F,T> ParallelMap<V,F,T,I>
+where I:AtomicIterator<AtomicItem = V> + Send + Sized,
+F: Pipeline<V,Output=T>,
+V: Send + Sync,
+T:Send + Sync
+{
+    /// Collect the outputs satisfying the predicate in A and the others in B
+    pub fn partition<A,B,P>(self, predicate:P) -> (A,B)
+    where A: CollectTarget<T>,
+    B: CollectTarget<T>,
+    P: Fn(&T) -> bool + Send + Sync
+    {
+        let (a, b) = self.reduce_with(Partition::<A,B,P> { predicate, ab: PhantomData });
+        (A::finish(a), B::finish(b))
+    }
+}
+
+/// Unzip on ParallelMap giving pairs. The outputs keep the order of the source within each collection.
+/// ```
+/// use parallel_task::prelude::*;
+///
+/// let (keys, lens):(Vec<&str>,Vec<usize>) = vec!["a","bb","ccc"].parallel_iter().map(|val| (*val, val.len())).unzip();
+/// assert_eq!(keys,vec!["a","bb","ccc"]);
+/// assert_eq!(lens,vec![1,2,3])
+/// ```
+///
+impl<I,V,F,X,Y> ParallelMap<V,F,(X,Y),I>
+where I:AtomicIterator<AtomicItem = V> + Send + Sized,
+F: Pipeline<V,Output=(X,Y)>,
+V: Send + Sync,
+X:Send + Sync,
+Y:Send + Sync
+{
+    /// Collect the first part of each output in A and the second part in B
+    pub fn unzip<A,B>(self) -> (A,B)
+    where A: CollectTarget<X>,
+    B: CollectTarget<Y>
+    {
+        let (a, b) = self.reduce_with(Unzip::<A,B> { ab: PhantomData });
+        (A::finish(a), B::finish(b))
+    }
+}
+
+/// ParallelPartitionIter allows partition to be called directly on type implementing AtomicIterator.
+/// ```
+/// use std::collections::HashSet;
+/// use parallel_task::prelude::*;
+///
+/// let vec = (0..1_000).collect::<Vec<u32>>();
+/// let (small, large):(Vec<&u32>,HashSet<&u32>) = vec.parallel_iter().partition(|val| **val < 100);
+/// assert_eq!(small.len(),100);
+/// assert_eq!(large.len(),900);
+/// ```
+///
+pub trait ParallelPartitionIter<V>
+where Self: AtomicIterator<AtomicItem = V> + Send + Sized,
+V: Send + Sync
+{
+    fn partition<A,B,P>(self, predicate:P) -> (A,B)
+    where A: CollectTarget<V>,
+    B: CollectTarget<V>,
+    P: Fn(&V) -> bool + Send + Sync {
+        ParallelMap::new(self, Identity::default()).partition(predicate)
+    }
+}
+
+impl<I,V> ParallelPartitionIter<V> for I
+where I: AtomicIterator<AtomicItem = V> + Send + Sized,
+V: Send + Sync {}
+
+/// ParallelUnzipIter allows unzip to be called directly on type implementing AtomicIterator over pairs.
+/// ```
+/// use std::collections::HashMap;
+/// use parallel_task::prelude::*;
+///
+/// let map = (0..100).map(|val| (val, val * 10)).collect::<HashMap<i32,i32>>();
+/// let (mut keys, mut vals):(Vec<i32>,Vec<i32>) = map.into_parallel_iter().unzip();
+/// keys.sort();
+/// vals.sort();
+/// assert_eq!(keys,(0..100).collect::<Vec<i32>>());
+/// assert_eq!(vals,(0..100).map(|val| val * 10).collect::<Vec<i32>>());
+/// ```
+///
+pub trait ParallelUnzipIter<X,Y>
+where Self: AtomicIterator<AtomicItem = (X,Y)> + Send + Sized,
+X: Send + Sync,
+Y: Send + Sync
+{
+    fn unzip<A,B>(self) -> (A,B)
+    where A: CollectTarget<X>,
+    B: CollectTarget<Y> {
+        ParallelMap::new(self, Identity::default()).unzip()
+    }
+}
+
+impl<I,X,Y> ParallelUnzipIter<X,Y> for I
+where I: AtomicIterator<AtomicItem = (X,Y)> + Send + Sized,
+X: Send + Sync,
+Y: Send + Sync {}
@@ -2,17 +2,37 @@ use std::hint::spin_loop;
 use statrs::distribution::{ContinuousCDF, Normal};
 
 const DEFAULT_THREADS_NUM:usize = 1;
-#[allow(dead_code)]
-const CPU_2_THREAD_RATIO:usize = 2;
+pub const CPU_2_THREAD_RATIO:usize = 2;
+/// Environment variable that caps the number of threads used by any thread pool
+pub const NUM_THREADS_ENV:&str = "PARALLEL_TASK_NUM_THREADS";
 
 pub fn max_threads() -> usize {        
+    max_threads_for_ratio(CPU_2_THREAD_RATIO)
+}
+
+/// Number of threads for the given ratio of threads per CPU available, capped by PARALLEL_TASK_NUM_THREADS if set.
+pub fn max_threads_for_ratio(cpu_to_thread_ratio:usize) -> usize {
     let num_threads:usize = if let Ok(available_cpus) = std::thread::available_parallelism() {
-        available_cpus.get() * CPU_2_THREAD_RATIO
+        usize::max(available_cpus.get() * cpu_to_thread_ratio, DEFAULT_THREADS_NUM)
     } else {
         DEFAULT_THREADS_NUM
     };
 
-    num_threads
+    cap_to_env_limit(num_threads)
+}
+
+/// Caps the thread count to the value of PARALLEL_TASK_NUM_THREADS. Values that are not a positive integer are ignored.
+pub fn cap_to_env_limit(num_threads:usize) -> usize {
+    match env_thread_limit() {
+        Some(limit) => usize::min(num_threads, limit),
+        None => num_threads
+    }
+}
+
+fn env_thread_limit() -> Option<usize> {
+    std::env::var(NUM_THREADS_ENV).ok()
+    .and_then(|val| val.trim().parse::<usize>().ok())
+    .filter(|val| *val > 0)
 }
 
 /// SpinWait struct is used wherever atomics are used to control resource access over a normal locking approach.
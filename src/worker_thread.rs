@@ -2,30 +2,70 @@
 //! spawns WorkerThreads. These worker threads can be communicated with via sync and async channels to 
 //! send data for processing and to close the same
 
-use crate::{collector::Collector, errors::WorkThreadError, for_each::ParallelForEach, iterators::iterator::AtomicIterator, map::ParallelMap, push_workers::{priorisation::ThreadPrioritization, worker_controller::WorkerController}};
-pub struct WorkerThreads {pub nthreads:usize }
+use crate::{collector::CollectTarget, errors::WorkThreadError, for_each::ParallelForEach, iterators::iterator::AtomicIterator, map::ParallelMap, pipeline::Pipeline, push_workers::{priorisation::ThreadPrioritization, thread_pool::ThreadPool, thread_runner::Halt, worker_controller::WorkerController}, reduce::Reducer};
+pub struct WorkerThreads {pub nthreads:usize, pub pool:ThreadPool }
 
 #[allow(dead_code)]
 impl WorkerThreads
 {
     pub fn collect<I,F,T,V,C>(self, task:ParallelMap<V,F,T,I>) -> C
     where I:AtomicIterator<AtomicItem = V> + Send + Sized,
-    F: Fn(V) -> T + Send + Sync,
+    F: Pipeline<V,Output=T>,
     V: Send + Sync,
     T:Send + Sync,
-    C: Collector<T> {          
+    C: CollectTarget<T> {          
+        self.run_controller(task, C::halt().map(Halt::ordered))
+    }
+
+    /// Collects the outputs till one of them satisfies the halt check, after which all threads are stopped.
+    pub fn collect_until<I,F,T,V,C>(self, task:ParallelMap<V,F,T,I>, halt:fn(&T) -> bool) -> C
+    where I:AtomicIterator<AtomicItem = V> + Send + Sized,
+    F: Pipeline<V,Output=T>,
+    V: Send + Sync,
+    T:Send + Sync,
+    C: CollectTarget<T> {          
+        self.run_controller(task, Some(Halt::all(halt)))
+    }
+
+    /// Runs the reducer on the outputs within the threads and combines the accumulators in the order of the source.
+    /// A halt stops the threads, or skips the values after the one found, once an output satisfies its check.
+    pub fn reduce<I,F,T,V,R>(self, task:ParallelMap<V,F,T,I>, reducer:R, halt:Option<Halt<T>>) -> R::Acc
+    where I:AtomicIterator<AtomicItem = V> + Send + Sized,
+    F: Pipeline<V,Output=T>,
+    V: Send + Sync,
+    T:Send + Sync,
+    R: Reducer<T> {
+        let fnc = task.f;
+        let q = task.iter.iter;
+        let mut controller = WorkerController::new(fnc,q, ThreadPrioritization::Remaining);
+        controller.set_pool(&self.pool);
+        controller.set_max_threads(self.nthreads);
+        if let Some(halt) = halt {
+            controller.set_halt_check(halt);
+        }
+        match controller.reduce(reducer) {
+            Ok(res) => { res }
+            Err(e) => Self::raise(e)
+        }
+    }
+
+    fn run_controller<I,F,T,V,C>(self, task:ParallelMap<V,F,T,I>, halt:Option<Halt<T>>) -> C
+    where I:AtomicIterator<AtomicItem = V> + Send + Sized,
+    F: Pipeline<V,Output=T>,
+    V: Send + Sync,
+    T:Send + Sync,
+    C: CollectTarget<T> {          
         let fnc = task.f;       
         let q = task.iter.iter;        
-        match WorkerController::new(fnc,q, ThreadPrioritization::Remaining)
-        .run::<C>() {
+        let mut controller = WorkerController::new(fnc,q, ThreadPrioritization::Remaining);
+        controller.set_pool(&self.pool);
+        controller.set_max_threads(self.nthreads);
+        if let Some(halt) = halt {
+            controller.set_halt_check(halt);
+        }
+        match controller.run::<C>() {
             Ok(res) => { res }
-            Err(e) => {
-                if let WorkThreadError::ThreadAdd(e) = e {
-                    panic!("Error: {}",e);
-                } else {
-                    panic!("Unknown error occurred in worker controller");
-                }
-            }
+            Err(e) => Self::raise(e)
         }               
     }  
 
@@ -37,15 +77,22 @@ impl WorkerThreads
         let fnc = task.f;       
         let q = task.iter.iter;            
 
-        if let Err(e) = WorkerController::new(fnc,q,ThreadPrioritization::Remaining)
-         .run::<Vec<_>>() {            
-            if let WorkThreadError::ThreadAdd(e) = e {
-                panic!("Error: {}",e);
-            } else {
-                panic!("Unknown error occurred in worker controller");
-            }        
+        let mut controller = WorkerController::new(fnc,q,ThreadPrioritization::Remaining);
+        controller.set_pool(&self.pool);
+        controller.set_max_threads(self.nthreads);
+        if let Err(e) = controller.run::<Vec<_>>() {            
+            Self::raise(e)
         };             
     }    
+
+    /// Panics within a user function are re-raised on the calling thread with the original payload.
+    fn raise(e:WorkThreadError) -> ! {
+        match e {
+            WorkThreadError::WorkerPanic { payload, .. } => std::panic::resume_unwind(payload),
+            WorkThreadError::ThreadAdd(e) => panic!("Error: {}",e),
+            _ => panic!("Unknown error occurred in worker controller")
+        }
+    }
 }
 
 
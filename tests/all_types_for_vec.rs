@@ -12,7 +12,7 @@ fn all_types_test_for_vec() {
 
     // function
     let job = |v:i32| v + 10;
-    let v = (0..100_000).map(|_| job.clone() ).collect::<Vec<_>>()
+    let v = (0..100_000).map(|_| job ).collect::<Vec<_>>()
     .into_parallel_iter().map(|x|x(10)).collect::<Vec<_>>();
     assert_eq!(v.len(),100_000);
 
@@ -0,0 +1,59 @@
+use std::io::{BufRead, Cursor};
+use parallel_task::prelude::*;
+use parallel_task::iterators::{bridge::IterBridge, prelude::DiscreteQueue};
+
+#[test]
+fn bridge_order_test() {
+    let res = (0..100_000u64).filter(|val| val % 7 != 0).par_bridge().map(|val| val * 3).collect::<Vec<u64>>();
+    assert_eq!(res,(0..100_000u64).filter(|val| val % 7 != 0).map(|val| val * 3).collect::<Vec<u64>>());
+}
+
+#[test]
+fn bridge_empty_and_short_test() {
+    assert_eq!(std::iter::empty::<i32>().par_bridge().map(|val| val).collect::<Vec<i32>>(),Vec::<i32>::new());
+    assert_eq!(std::iter::once(5).par_bridge().map(|val| val + 1).collect::<Vec<i32>>(),vec![6]);
+    assert_eq!((0..16).par_bridge().count(),16);
+    assert_eq!((0..17).par_bridge().count(),17);
+}
+
+#[test]
+fn bridge_unfused_test() {
+    // The iterator gives values again after its first None, which must not be taken
+    let mut next = 0;
+    let unfused = std::iter::from_fn(move || {
+        next += 1;
+        if next == 3 { None } else { Some(next) }
+    });
+    let mut bridge = IterBridge::new(unfused);
+    assert_eq!(std::iter::from_fn(|| bridge.pop()).collect::<Vec<i32>>(),vec![1,2]);
+    assert!(!bridge.is_active());
+    assert_eq!(bridge.pull(),None);
+}
+
+#[test]
+fn bridge_lines_test() {
+    let text = (0..5_000).map(|val| val.to_string()).collect::<Vec<String>>().join("\n");
+    let reader = Cursor::new(text.into_bytes());
+    let total = reader.lines().par_bridge().map(|line| line.unwrap().parse::<u64>().unwrap()).sum::<u64>();
+    assert_eq!(total,(0..5_000).sum::<u64>());
+}
+
+#[test]
+fn bridge_uneven_jobs_test() {
+    // Slow values keep the threads busy while the rest of the iterator is still being pulled
+    let res = (0..20_000u64).par_bridge()
+    .map(|val| {
+        if val % 11 == 0 { std::thread::sleep(std::time::Duration::from_micros(20)); }
+        val
+    })
+    .fold(|| 0u64,|acc,val| acc + val,|a,b| a + b);
+    assert_eq!(res,(0..20_000u64).sum::<u64>());
+}
+
+#[test]
+fn bridge_search_test() {
+    // Searching an endless iterator stops pulling once the value is found
+    let res = (0u64..).par_bridge().find_first(|val| *val == 12_345);
+    assert_eq!(res,Some(12_345));
+    assert!((0u64..).par_bridge().any(|val| val > 50_000));
+}
@@ -0,0 +1,48 @@
+use parallel_task::prelude::*;
+
+#[test]
+fn chunks_test() {
+    let vec = (0..100_003).collect::<Vec<u64>>();
+    let res = vec.par_chunks(1_000).map(|chunk| chunk.iter().sum::<u64>()).collect::<Vec<u64>>();
+    assert_eq!(res,vec.chunks(1_000).map(|chunk| chunk.iter().sum::<u64>()).collect::<Vec<u64>>());
+    assert_eq!(vec.par_chunks(1_000).count(),101);
+}
+
+#[test]
+fn chunks_stay_whole_test() {
+    // Uneven job lengths force redistribution, which moves whole chunks between threads
+    let vec = (0..50_000).collect::<Vec<u32>>();
+    let res = vec.par_chunks(10).map(|chunk| {
+        if chunk[0] % 70 == 0 { std::thread::sleep(std::time::Duration::from_micros(50)); }
+        (chunk[0], chunk.len())
+    }).collect::<Vec<(u32,usize)>>();
+    assert_eq!(res,(0..5_000).map(|idx| (idx * 10, 10)).collect::<Vec<(u32,usize)>>());
+}
+
+#[test]
+fn chunks_exact_test() {
+    let vec = (0..10).collect::<Vec<i32>>();
+    let res = vec.par_chunks_exact(3).map(|chunk| chunk.to_vec()).collect::<Vec<Vec<i32>>>();
+    assert_eq!(res,vec![vec![0,1,2],vec![3,4,5],vec![6,7,8]]);
+    assert_eq!(vec[..2].par_chunks_exact(3).count(),0);
+}
+
+#[test]
+fn chunks_mut_test() {
+    let mut vec = vec![0u64; 100_000];
+    vec.par_chunks_mut(256).for_each(|chunk| {
+        let len = chunk.len() as u64;
+        chunk.iter_mut().for_each(|val| *val = len);
+    });
+    assert!(vec[..99_840].iter().all(|val| *val == 256));
+    assert!(vec[99_840..].iter().all(|val| *val == 160));
+}
+
+#[test]
+fn windows_test() {
+    let vec = (0..20_000).map(|val| (val * val) as i64).collect::<Vec<i64>>();
+    let res = vec.par_windows(3).map(|win| win[2] - 2 * win[1] + win[0]).collect::<Vec<i64>>();
+    assert_eq!(res.len(),19_998);
+    assert!(res.iter().all(|val| *val == 2));
+    assert_eq!(vec[..2].par_windows(3).count(),0);
+}
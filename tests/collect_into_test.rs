@@ -0,0 +1,61 @@
+use parallel_task::prelude::*;
+
+#[test]
+fn collect_into_vec_test() {
+    let vec = (0..100_000).collect::<Vec<i64>>();
+    let mut out = Vec::with_capacity(100_000);
+    let ptr = out.as_ptr();
+    vec.parallel_iter().map(|val| val * 3).collect_into_vec(&mut out);
+    assert_eq!(out,vec.iter().map(|val| val * 3).collect::<Vec<i64>>());
+    // The reserved capacity is written to directly
+    assert_eq!(out.as_ptr(),ptr);
+}
+
+#[test]
+fn collect_into_vec_replaces_test() {
+    let mut out = vec!["old".to_owned(); 50];
+    (0..1_000).into_parallel_iter().map(|val:i32| val.to_string()).collect_into_vec(&mut out);
+    assert_eq!(out,(0..1_000).map(|val| val.to_string()).collect::<Vec<String>>());
+    (0..0).into_parallel_iter().map(|val:i32| val.to_string()).collect_into_vec(&mut out);
+    assert!(out.is_empty());
+}
+
+#[test]
+fn collect_into_slice_test() {
+    // Uneven job lengths force the values to be redistributed across threads
+    let mut buffer = vec![String::new(); 20_000];
+    let input = (0..20_000).collect::<Vec<u32>>();
+    input[..].parallel_iter().map(|val| {
+        if val % 13 == 0 { std::thread::sleep(std::time::Duration::from_micros(20)); }
+        val
+    }).map(|val| format!("#{}", val)).collect_into_slice(&mut buffer[..]);
+    assert_eq!(buffer,input.iter().map(|val| format!("#{}", val)).collect::<Vec<String>>());
+}
+
+#[test]
+fn collect_into_range_test() {
+    let mut buffer = [0u16; 256];
+    (0..=255u8).into_parallel_iter().map(|val| val as u16 * 2).collect_into_slice(&mut buffer);
+    assert!(buffer.iter().enumerate().all(|(idx, val)| *val == idx as u16 * 2));
+}
+
+#[test]
+#[should_panic(expected = "slice length does not match")]
+fn collect_into_slice_length_test() {
+    let mut buffer = [0i32; 10];
+    (0..20).into_parallel_iter().map(|val| val).collect_into_slice(&mut buffer);
+}
+
+#[test]
+fn collect_into_combined_test() {
+    // Chained sources are pulled over several pulls, and each output still lands at the index of its value
+    let vec = (0..1_000).collect::<Vec<u32>>();
+    let mut out = Vec::new();
+    vec.parallel_iter().chain(vec.parallel_iter()).enumerate().map(|(idx, val)| idx as u32 - val).collect_into_vec(&mut out);
+    assert_eq!(out.len(),2_000);
+    assert!(out[..1_000].iter().all(|val| *val == 0));
+    assert!(out[1_000..].iter().all(|val| *val == 1_000));
+    let mut out = Vec::new();
+    vec.parallel_iter().zip((0..10u32).into_parallel_iter()).map(|(a, b)| a + b).collect_into_vec(&mut out);
+    assert_eq!(out,(0..10).map(|val| val * 2).collect::<Vec<u32>>());
+}
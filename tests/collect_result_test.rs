@@ -0,0 +1,46 @@
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use parallel_task::prelude::*;
+
+#[test]
+fn collect_result_ok_test() {
+    let vec = (0..50_000).map(|val| val.to_string()).collect::<Vec<String>>();
+    let res = vec.parallel_iter().map(|val| val.parse::<u32>()).collect::<Result<Vec<u32>,_>>();
+    assert_eq!(res,Ok((0..50_000).collect::<Vec<u32>>()));
+    let res = vec.parallel_iter().map(|val| val.parse::<u32>().map(|val| val % 10)).collect::<Result<HashSet<u32>,_>>();
+    assert_eq!(res,Ok((0..10).collect::<HashSet<u32>>()));
+}
+
+#[test]
+fn collect_result_first_err_test() {
+    // Several errors with uneven job lengths, the first in the order of the source is given as with std
+    let vec = (0..20_000u64).collect::<Vec<_>>();
+    let res = vec.parallel_iter().map(|val| {
+        if val % 7 == 0 { std::thread::sleep(std::time::Duration::from_micros(20)); }
+        if val % 4_001 == 4_000 { Err(*val) } else { Ok(*val) }
+    }).collect::<Result<Vec<u64>,u64>>();
+    assert_eq!(res,Err(4_000));
+}
+
+#[test]
+fn collect_result_stops_early_test() {
+    let processed = AtomicUsize::new(0);
+    let vec = (0..1_000_000).collect::<Vec<u64>>();
+    let res = vec.parallel_iter().map(|val| {
+        processed.fetch_add(1, Ordering::Relaxed);
+        if *val == 10 { Err("ten") } else { Ok(*val) }
+    }).collect::<Result<Vec<u64>,&str>>();
+    assert_eq!(res,Err("ten"));
+    assert!(processed.load(Ordering::Relaxed) < vec.len());
+}
+
+#[test]
+fn collect_option_test() {
+    let vec = (1..10_000).collect::<Vec<i32>>();
+    let res = vec.parallel_iter().map(|val| 100_000i32.checked_div(*val)).collect::<Option<Vec<i32>>>();
+    assert_eq!(res,Some(vec.iter().map(|val| 100_000 / val).collect::<Vec<i32>>()));
+    let res = vec.parallel_iter().map(|val| if *val == 5_000 { None } else { Some('x') }).collect::<Option<String>>();
+    assert_eq!(res,None);
+    let empty:Vec<i32> = Vec::new();
+    assert_eq!(empty.parallel_iter().map(|val| Some(*val)).collect::<Option<Vec<i32>>>(),Some(Vec::new()));
+}
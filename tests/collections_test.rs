@@ -0,0 +1,121 @@
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashSet, LinkedList, VecDeque};
+use parallel_task::prelude::*;
+use parallel_task::iterators::{queued::BatchQueue, prelude::DiscreteQueue};
+
+#[test]
+fn vecdeque_test() {
+    // Push to both ends so that the values are split across the buffer
+    let mut deque = VecDeque::new();
+    (0..5_000).for_each(|val| { deque.push_back(val); deque.push_front(-val - 1); });
+    let res = deque.parallel_iter().map(|val| *val).collect::<Vec<i32>>();
+    assert_eq!(res,deque.iter().copied().collect::<Vec<i32>>());
+
+    deque.parallel_iter_mut().for_each(|val| *val *= 2);
+    assert_eq!(deque.iter().copied().collect::<Vec<i32>>(),res.iter().map(|val| val * 2).collect::<Vec<i32>>());
+
+    let res = deque.clone().into_parallel_iter().map(|val| val + 1).collect::<Vec<i32>>();
+    assert_eq!(res,deque.iter().map(|val| val + 1).collect::<Vec<i32>>());
+}
+
+#[test]
+fn vecdeque_drained_test() {
+    let deque = (0..3).collect::<VecDeque<i32>>();
+    let mut iter = deque.parallel_iter();
+    assert_eq!(std::iter::from_fn(|| iter.atomic_next()).count(),3);
+    // Taking past the end leaves the iterator drained rather than out of bounds
+    assert_eq!(iter.atomic_next(),None);
+    assert!(!iter.is_active());
+    assert_eq!(iter.map(|val| *val).collect::<Vec<i32>>(),Vec::<i32>::new());
+
+    let vec = vec![1, 2];
+    let mut iter = vec.parallel_iter();
+    assert_eq!(std::iter::from_fn(|| iter.atomic_next()).count(),2);
+    assert!(!iter.is_active());
+}
+
+#[test]
+fn binary_heap_test() {
+    let heap = (0..10_000).collect::<BinaryHeap<u64>>();
+    assert_eq!(heap.parallel_iter().sum::<u64>(),(0..10_000).sum::<u64>());
+    let mut res = heap.into_parallel_iter().map(|val| val * 2).collect::<Vec<u64>>();
+    res.sort();
+    assert_eq!(res,(0..10_000).map(|val| val * 2).collect::<Vec<u64>>());
+}
+
+#[test]
+fn hashset_test() {
+    let set = (0..10_000).collect::<HashSet<i32>>();
+    let res = set.parallel_iter().map(|val| *val).collect::<Vec<i32>>().into_iter().collect::<HashSet<i32>>();
+    assert_eq!(res,set);
+    assert_eq!(set.into_parallel_iter().map(|val| val as i64).sum::<i64>(),(0..10_000).sum::<i64>());
+}
+
+#[test]
+fn btreeset_test() {
+    let set = (0..10_000).map(|val| val.to_string()).collect::<BTreeSet<String>>();
+    let res = set.parallel_iter().map(|val| val.clone()).collect::<Vec<String>>();
+    assert_eq!(res,set.iter().cloned().collect::<Vec<String>>());
+    let res = set.clone().into_parallel_iter().map(|val| val).collect::<Vec<String>>();
+    assert_eq!(res,set.into_iter().collect::<Vec<String>>());
+}
+
+#[test]
+fn btreemap_test() {
+    let mut map = (0..10_000).map(|val| (val, val * 10)).collect::<BTreeMap<i32,i32>>();
+    let res = map.parallel_iter().map(|(key, val)| key + val).collect::<Vec<i32>>();
+    assert_eq!(res,map.iter().map(|(key, val)| key + val).collect::<Vec<i32>>());
+    map.parallel_iter_mut().for_each(|(key, val)| *val -= key);
+    assert!(map.iter().all(|(key, val)| *val == key * 9));
+    let res = map.clone().into_parallel_iter().map(|val| val).collect::<Vec<(i32,i32)>>();
+    assert_eq!(res,map.into_iter().collect::<Vec<(i32,i32)>>());
+}
+
+#[test]
+fn linked_list_test() {
+    let mut list = (0..10_000).collect::<LinkedList<i64>>();
+    list.parallel_iter_mut().for_each(|val| *val += 1);
+    let res = list.parallel_iter().map(|val| *val).collect::<Vec<i64>>();
+    assert_eq!(res,(1..10_001).collect::<Vec<i64>>());
+    assert_eq!(list.into_parallel_iter().count(),10_000);
+}
+
+#[test]
+fn large_sequential_collections_test() {
+    // Collections stepped through in sequence are pulled in batches, which must still come out in their order
+    let map = (0..200_000u64).map(|val| (val, val * 3)).collect::<BTreeMap<u64,u64>>();
+    let res = map.parallel_iter().map(|(key, val)| key + val).collect::<Vec<u64>>();
+    assert_eq!(res,map.iter().map(|(key, val)| key + val).collect::<Vec<u64>>());
+    let res = map.clone().into_parallel_iter().map(|(key, _)| key).collect::<Vec<u64>>();
+    assert_eq!(res,(0..200_000u64).collect::<Vec<u64>>());
+
+    let set = (0..200_000u64).collect::<BTreeSet<u64>>();
+    assert_eq!(set.parallel_iter().map(|val| *val).collect::<Vec<u64>>(),(0..200_000u64).collect::<Vec<u64>>());
+    assert_eq!(set.into_parallel_iter().map(|val| val).collect::<Vec<u64>>(),(0..200_000u64).collect::<Vec<u64>>());
+
+    let set = (0..200_000u64).collect::<HashSet<u64>>();
+    assert_eq!(set.parallel_iter().map(|val| *val).collect::<Vec<u64>>(),set.iter().copied().collect::<Vec<u64>>());
+    assert_eq!(set.clone().into_parallel_iter().map(|val| val).collect::<Vec<u64>>(),set.into_iter().collect::<Vec<u64>>());
+
+    let mut list = (0..200_000u64).collect::<LinkedList<u64>>();
+    list.parallel_iter_mut().for_each(|val| *val *= 2);
+    assert_eq!(list.parallel_iter().map(|val| *val).collect::<Vec<u64>>(),(0..200_000u64).map(|val| val * 2).collect::<Vec<u64>>());
+    assert_eq!(list.into_parallel_iter().map(|val| val).collect::<Vec<u64>>(),(0..200_000u64).map(|val| val * 2).collect::<Vec<u64>>());
+}
+
+#[test]
+fn batch_queue_test() {
+    // The values are pulled in bounded batches rather than all at once
+    let set = (0..200_000u64).collect::<BTreeSet<u64>>();
+    let mut queue = BatchQueue::new(set.iter(), set.len());
+    assert!(queue.is_pending());
+    let mut sizes = Vec::new();
+    while let Some(batch) = queue.pull() {
+        sizes.push(batch.len());
+    }
+    // Batches start small and double in size, with none close to the length of the collection
+    assert_eq!(sizes[1],sizes[0] * 2);
+    assert!(sizes.iter().all(|size| *size < 200_000 / 10));
+    assert_eq!(sizes.iter().sum::<usize>(),200_000);
+    assert!(!queue.is_active());
+    assert_eq!(queue.len(),Some(0));
+}
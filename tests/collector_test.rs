@@ -0,0 +1,69 @@
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashSet, LinkedList};
+use parallel_task::prelude::*;
+
+#[test]
+fn hashset_collect_test() {
+    let vec = (0..100_000).collect::<Vec<i32>>();
+    let res = vec.parallel_iter().map(|val| val % 1_000).collect::<HashSet<i32>>();
+    assert_eq!(res,(0..1_000).collect::<HashSet<i32>>());
+}
+
+#[test]
+fn btree_collect_test() {
+    let vec = (0..50_000).collect::<Vec<u64>>();
+    let res = vec.parallel_iter().map(|val| (val % 500, *val)).collect::<BTreeMap<u64,u64>>();
+    // Later outputs overwrite earlier ones, as with a serial collect
+    assert_eq!(res,vec.iter().map(|val| (val % 500, *val)).collect::<BTreeMap<u64,u64>>());
+    let res = vec.parallel_iter().map(|val| val.to_string()).collect::<BTreeSet<String>>();
+    assert_eq!(res.len(),50_000);
+    assert_eq!(res.first().map(|val| val.as_str()),Some("0"));
+}
+
+#[test]
+fn binary_heap_collect_test() {
+    let vec = (0..10_000).collect::<Vec<i64>>();
+    let res = vec.parallel_iter().map(|val| (val * 7919) % 10_007).collect::<BinaryHeap<i64>>();
+    assert_eq!(res.into_sorted_vec(),vec.iter().map(|val| (val * 7919) % 10_007).collect::<BinaryHeap<i64>>().into_sorted_vec());
+}
+
+#[test]
+fn linked_list_collect_test() {
+    let vec = (0..20_000).collect::<Vec<i32>>();
+    let res = vec.parallel_iter().filter(|val| **val % 3 == 0).map(|val| *val).collect::<LinkedList<i32>>();
+    assert!(res.into_iter().eq((0..20_000).filter(|val| val % 3 == 0)));
+}
+
+#[test]
+fn string_collect_order_test() {
+    // Uneven job lengths force the partial strings of several threads to be merged
+    let vec = (0..5_000).collect::<Vec<u32>>();
+    let res = vec.parallel_iter().map(|val| {
+        if val % 17 == 0 { std::thread::sleep(std::time::Duration::from_micros(20)); }
+        char::from_u32(0x61 + val % 26).unwrap()
+    }).collect::<String>();
+    assert_eq!(res,vec.iter().map(|val| char::from_u32(0x61 + val % 26).unwrap()).collect::<String>());
+}
+
+/// Collection that only gives initialize, as collectors outside the crate may do
+struct Joined(Vec<i32>);
+
+impl Extend<i32> for Joined {
+    fn extend<I: IntoIterator<Item = i32>>(&mut self, iter:I) {
+        self.0.extend(iter);
+    }
+}
+
+impl parallel_task::collector::Collector<i32> for Joined {
+    fn initialize() -> Self {
+        Joined(Vec::new())
+    }
+}
+
+#[test]
+fn extend_collect_test() {
+    let vec = (0..50_000).collect::<Vec<i32>>();
+    let res = vec.parallel_iter().map(|val| val * 2).collect::<Joined>();
+    assert_eq!(res.0,(0..50_000).map(|val| val * 2).collect::<Vec<i32>>());
+    let res = vec.parallel_iter().map(|val| if *val < 60_000 { Ok(*val) } else { Err(*val) }).collect::<Result<Joined,i32>>();
+    assert_eq!(res.map(|res| res.0.len()),Ok(50_000));
+}
@@ -0,0 +1,14 @@
+use parallel_task::prelude::*;
+
+// Kept in its own test binary as the environment variable applies to the whole process
+#[test]
+fn env_num_threads_test() {
+    std::env::set_var("PARALLEL_TASK_NUM_THREADS", "2");
+    assert_eq!(ThreadPoolBuilder::new().build().max_threads(),2);
+    assert_eq!(ThreadPoolBuilder::new().num_threads(16).build().max_threads(),2);
+    assert_eq!(ThreadPool::global().max_threads(),2);
+
+    std::env::set_var("PARALLEL_TASK_NUM_THREADS", "not a number");
+    assert_eq!(ThreadPoolBuilder::new().num_threads(16).build().max_threads(),16);
+    assert!(ThreadPoolBuilder::new().build_global().is_err());
+}
@@ -0,0 +1,66 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use parallel_task::prelude::*;
+
+#[test]
+fn filter_map_chain_test() {
+    let vec = (0..100_000).collect::<Vec<i32>>();
+    let res = vec.parallel_iter().filter(|val| **val % 3 == 0).map(|val| val * 2).collect::<Vec<_>>();
+    let test = vec.iter().filter(|val| **val % 3 == 0).map(|val| val * 2).collect::<Vec<_>>();
+    assert_eq!(res,test);
+}
+
+#[test]
+fn map_filter_map_chain_test() {
+    let res = (0..100_000i64).into_parallel_iter()
+    .map(|val| val - 50_000)
+    .filter(|val| *val > 0)
+    .map(|val| val.to_string())
+    .collect::<Vec<_>>();
+    let test = (0..100_000i64).map(|val| val - 50_000).filter(|val| *val > 0).map(|val| val.to_string()).collect::<Vec<_>>();
+    assert_eq!(res,test);
+}
+
+#[test]
+fn filter_map_test() {
+    let vec = (0..10_000).map(|val| if val % 10 == 0 { "x".to_owned() } else { val.to_string() }).collect::<Vec<_>>();
+    let res = vec.parallel_iter().filter_map(|val| val.parse::<i32>().ok()).collect::<Vec<_>>();
+    let test = vec.iter().filter_map(|val| val.parse::<i32>().ok()).collect::<Vec<_>>();
+    assert_eq!(res,test);
+}
+
+#[test]
+fn flat_map_order_test() {
+    let vec = (0..2_000usize).collect::<Vec<_>>();
+    let res = vec.parallel_iter().flat_map(|val| 0..(*val % 5)).collect::<Vec<_>>();
+    let test = vec.iter().flat_map(|val| 0..(*val % 5)).collect::<Vec<_>>();
+    assert_eq!(res,test);
+}
+
+#[test]
+fn flat_map_then_filter_test() {
+    let res = (0..1_000u32).into_parallel_iter()
+    .map(|val| val * 2)
+    .flat_map(|val| vec![val, val + 1])
+    .filter(|val| val % 3 != 0)
+    .collect::<Vec<_>>();
+    let test = (0..1_000u32).map(|val| val * 2).flat_map(|val| vec![val, val + 1]).filter(|val| val % 3 != 0).collect::<Vec<_>>();
+    assert_eq!(res,test);
+}
+
+#[test]
+fn filter_for_each_test() {
+    let count = AtomicUsize::new(0);
+    (0..100_000).collect::<Vec<i32>>().parallel_iter()
+    .filter(|val| **val % 2 == 0)
+    .for_each(|_| { count.fetch_add(1, Ordering::Relaxed); });
+    assert_eq!(count.load(Ordering::Relaxed),50_000);
+}
+
+#[test]
+fn filter_try_collect_test() {
+    let res = (0..100_000).collect::<Vec<i32>>().parallel_iter()
+    .filter(|val| **val % 2 == 1)
+    .map(|val| if *val == 777 { Err(*val) } else { Ok(*val) })
+    .try_collect::<Vec<_>>();
+    assert_eq!(res,Err(777));
+}
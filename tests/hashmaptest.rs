@@ -9,7 +9,7 @@ fn hashmap_test() {
         (0..1_000).sum::<i32>()
     };
 
-    let hashmap_jobs = (0..100_00).map(|i|(i,job)).collect::<HashMap<_,_>>();
+    let hashmap_jobs = (0..10_000).map(|i|(i,job)).collect::<HashMap<_,_>>();
 
     let h1 = hashmap_jobs.parallel_iter().
     map(|(_,job)|job())
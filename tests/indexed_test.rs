@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+use parallel_task::prelude::*;
+
+#[test]
+fn enumerate_test() {
+    let vec = (0..50_000).map(|val| val * 3).collect::<Vec<u64>>();
+    let res = vec.parallel_iter().enumerate().map(|(idx, val)| idx as u64 * 3 == *val).all(|ok| ok);
+    assert!(res);
+    let res = vec.parallel_iter().enumerate().filter(|(_, val)| **val % 7 == 0).map(|(idx, _)| idx).collect::<Vec<usize>>();
+    assert_eq!(res,vec.iter().enumerate().filter(|(_, val)| **val % 7 == 0).map(|(idx, _)| idx).collect::<Vec<usize>>());
+    let res = (100..200usize).into_parallel_iter().enumerate().map(|(idx, val)| val - idx).collect::<Vec<usize>>();
+    assert!(res.iter().all(|val| *val == 100));
+}
+
+#[test]
+fn zip_test() {
+    let a = (0..100_000).collect::<Vec<i64>>();
+    let b = (0..100_000).map(|val| val * 2).collect::<Vec<i64>>();
+    let res = a.parallel_iter().zip(b.parallel_iter()).map(|(x, y)| x + y).collect::<Vec<i64>>();
+    assert_eq!(res,(0..100_000).map(|val| val * 3).collect::<Vec<i64>>());
+    let dot = a.parallel_iter().zip(b.parallel_iter()).map(|(x, y)| x * y).sum::<i64>();
+    assert_eq!(dot,a.iter().zip(b.iter()).map(|(x, y)| x * y).sum::<i64>());
+}
+
+#[test]
+fn zip_uneven_test() {
+    // The shorter source ends the pairs, as with Iterator::zip
+    let names = vec!["a", "b", "c", "d"];
+    let res = names.parallel_iter().zip((0..3).into_parallel_iter()).map(|(name, val)| format!("{}{}", name, val)).collect::<Vec<String>>();
+    assert_eq!(res,vec!["a0","b1","c2"]);
+    let map = (0..1_000).map(|val| (val, val)).collect::<HashMap<i32,i32>>();
+    let res = (0..10usize).into_parallel_iter().zip(map.parallel_iter()).count();
+    assert_eq!(res,10);
+}
+
+#[test]
+fn chain_test() {
+    let a = (0..30_000).collect::<Vec<u32>>();
+    let b = (30_000..50_000).collect::<Vec<u32>>();
+    let res = a.parallel_iter().chain(b.parallel_iter()).map(|val| val * 2).collect::<Vec<u32>>();
+    assert_eq!(res,(0..50_000).map(|val| val * 2).collect::<Vec<u32>>());
+    let res = a.parallel_iter().chain(b.parallel_iter()).enumerate().map(|(idx, val)| idx as u32 == *val).all(|ok| ok);
+    assert!(res);
+    let empty:Vec<u32> = Vec::new();
+    assert_eq!(empty.parallel_iter().chain(b.parallel_iter()).count(),20_000);
+    assert_eq!(a.clone().into_parallel_iter().chain((0..10u32).into_parallel_iter()).sum::<u32>(),a.iter().sum::<u32>() + 45);
+}
+
+#[test]
+fn pending_source_test() {
+    let vec = (0..1_000).collect::<Vec<u32>>();
+    assert!(!vec.parallel_iter().is_pending());
+    assert!(!vec.parallel_iter().enumerate().is_pending());
+    assert!((0..10u32).par_bridge().is_pending());
+    assert!(vec.parallel_iter().zip((0..10u32).par_bridge()).is_pending());
+    // The second source of a chain is pulled after the first, though both lengths are known
+    assert!(vec.parallel_iter().chain(vec.parallel_iter()).is_pending());
+    assert_eq!(vec.parallel_iter().chain(vec.parallel_iter()).zip((0..1_500u32).par_bridge()).count(),1_500);
+}
@@ -0,0 +1,43 @@
+use std::collections::HashMap;
+use parallel_task::prelude::*;
+
+#[test]
+fn vec_iter_mut_test() {
+    let mut vec = (0..100_000).collect::<Vec<i64>>();
+    vec.parallel_iter_mut().for_each(|val| *val *= 2);
+    assert_eq!(vec,(0..100_000).map(|val| val * 2).collect::<Vec<i64>>());
+}
+
+#[test]
+fn slice_iter_mut_test() {
+    let mut vec = (0..10_000).map(|val| val.to_string()).collect::<Vec<String>>();
+    vec[5_000..].parallel_iter_mut().for_each(|val| val.push('!'));
+    assert!(vec[..5_000].iter().all(|val| !val.ends_with('!')));
+    assert!(vec[5_000..].iter().all(|val| val.ends_with('!')));
+}
+
+#[test]
+fn uneven_jobs_iter_mut_test() {
+    // Uneven job lengths force the controller to redistribute the mutable references across threads
+    let mut vec = (0..5_000u64).collect::<Vec<_>>();
+    vec.parallel_iter_mut().for_each(|val| {
+        if *val % 3 == 0 { std::thread::sleep(std::time::Duration::from_micros(20)); }
+        *val += 1;
+    });
+    assert!(vec.iter().enumerate().all(|(idx, val)| *val == idx as u64 + 1));
+}
+
+#[test]
+fn iter_mut_map_test() {
+    let mut vec = (0..1_000).collect::<Vec<i32>>();
+    let old = vec.parallel_iter_mut().map(|val| std::mem::replace(val, -1)).collect::<Vec<i32>>();
+    assert_eq!(old,(0..1_000).collect::<Vec<i32>>());
+    assert!(vec.iter().all(|val| *val == -1));
+}
+
+#[test]
+fn hashmap_iter_mut_test() {
+    let mut map = (0..10_000).map(|val| (val, val.to_string())).collect::<HashMap<i32,String>>();
+    map.parallel_iter_mut().for_each(|(key, val)| if key % 2 == 0 { val.clear() });
+    assert!(map.iter().all(|(key, val)| (key % 2 == 0) == val.is_empty()));
+}
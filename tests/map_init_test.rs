@@ -0,0 +1,69 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use parallel_task::prelude::*;
+
+#[test]
+fn map_init_test() {
+    let inits = AtomicUsize::new(0);
+    let vec = (0..100_000).collect::<Vec<u64>>();
+    let res = vec.parallel_iter()
+    .map_init(|| { inits.fetch_add(1, Ordering::Relaxed); Vec::<u64>::with_capacity(8) }, |buf, val| {
+        buf.clear();
+        buf.extend([*val, *val]);
+        buf.iter().sum::<u64>()
+    })
+    .collect::<Vec<u64>>();
+    assert_eq!(res,(0..100_000).map(|val| val * 2).collect::<Vec<u64>>());
+    // init runs at most once per thread, not once per value
+    assert!(inits.load(Ordering::Relaxed) >= 1);
+    assert!(inits.load(Ordering::Relaxed) <= ThreadPool::global().max_threads());
+}
+
+#[test]
+fn map_init_chained_test() {
+    let inits = AtomicUsize::new(0);
+    let res = (0..10_000).into_parallel_iter()
+    .filter(|val| val % 2 == 0)
+    .map_init(|| { inits.fetch_add(1, Ordering::Relaxed); 0usize }, |count, val| { *count += 1; val })
+    .map(|val| val + 1)
+    .threads(4)
+    .collect::<Vec<i32>>();
+    assert_eq!(res,(0..10_000).filter(|val| val % 2 == 0).map(|val| val + 1).collect::<Vec<i32>>());
+    assert!(inits.load(Ordering::Relaxed) <= 4);
+}
+
+#[test]
+fn map_with_test() {
+    let res = vec!["a","b","c"].parallel_iter()
+    .map_with(String::from(">"), |prefix, val| format!("{}{}",prefix,val))
+    .collect::<Vec<String>>();
+    assert_eq!(res,vec![">a",">b",">c"]);
+    // Each thread works on its own clone of the seed
+    let res = (0..10_000u64).into_parallel_iter()
+    .map_with(Vec::<u64>::new(), |seen, val| { seen.push(val); seen.len() })
+    .collect::<Vec<usize>>();
+    assert!(res.iter().all(|len| (1..=10_000).contains(len)));
+    // The values run by a thread are counted only in its own clone
+    assert!(res.iter().filter(|len| **len == 1).count() <= ThreadPool::global().max_threads());
+}
+
+#[test]
+fn for_each_init_test() {
+    let inits = AtomicUsize::new(0);
+    let total = Mutex::new(0u64);
+    (0..50_000u64).into_parallel_iter().map(|val| val).threads(4).for_each_init(|| { inits.fetch_add(1, Ordering::Relaxed); Vec::new() }, |buf, val| {
+        buf.push(val);
+        if buf.len() == 100 {
+            *total.lock().unwrap() += buf.drain(..).sum::<u64>();
+        }
+    });
+    assert!(inits.load(Ordering::Relaxed) <= 4);
+    assert!(*total.lock().unwrap() <= (0..50_000u64).sum::<u64>());
+}
+
+#[test]
+fn map_init_collect_into_test() {
+    let mut out = Vec::new();
+    (0..1_000u32).into_parallel_iter().map_init(|| 3u32, |factor, val| val * *factor).collect_into_vec(&mut out);
+    assert_eq!(out,(0..1_000u32).map(|val| val * 3).collect::<Vec<u32>>());
+}
@@ -0,0 +1,47 @@
+use std::collections::HashMap;
+use parallel_task::prelude::*;
+
+#[test]
+fn parallel_iter_order_test() {
+    let vec = (0..100_000).collect::<Vec<i32>>();
+    let res = vec.parallel_iter().map(|val| val * 2).collect::<Vec<_>>();
+    let test = vec.iter().map(|val| val * 2).collect::<Vec<_>>();
+    assert_eq!(res,test);
+}
+
+#[test]
+fn into_parallel_iter_order_test() {
+    let vec = (0..100_000).map(|v| v.to_string()).collect::<Vec<String>>();
+    let res = vec.clone().into_parallel_iter().map(|val| val + "!").collect::<Vec<_>>();
+    let test = vec.into_iter().map(|val| val + "!").collect::<Vec<_>>();
+    assert_eq!(res,test);
+}
+
+#[test]
+fn range_order_test() {
+    let res = (0..100_000i64).into_parallel_iter().map(|val| val - 1).collect::<Vec<_>>();
+    let test = (0..100_000i64).map(|val| val - 1).collect::<Vec<_>>();
+    assert_eq!(res,test);
+}
+
+#[test]
+fn uneven_jobs_order_test() {
+    // Uneven job lengths force the controller to redistribute the queues across threads
+    let res = (0..2_000u64).collect::<Vec<_>>()
+    .into_parallel_iter()
+    .map(|val| {
+        if val % 7 == 0 { std::thread::sleep(std::time::Duration::from_micros(200)); }
+        val
+    })
+    .collect::<Vec<_>>();
+    assert_eq!(res,(0..2_000u64).collect::<Vec<_>>());
+}
+
+#[test]
+fn hashmap_order_test() {
+    // HashMap iteration has no inherent order but the results must follow the order of iter()
+    let map = (0..10_000).map(|i| (i, i * 3)).collect::<HashMap<_,_>>();
+    let res = map.parallel_iter().map(|(k,_)| *k).collect::<Vec<_>>();
+    let test = map.keys().copied().collect::<Vec<_>>();
+    assert_eq!(res,test);
+}
@@ -0,0 +1,65 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use parallel_task::{errors::WorkThreadError, prelude::*, push_workers::{priorisation::ThreadPrioritization, worker_controller::WorkerController}};
+
+#[test]
+fn map_panic_payload_test() {
+    let vec = (0..10_000).collect::<Vec<i32>>();
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        vec.parallel_iter()
+        .map(|val| if *val == 5_000 { panic!("failed on value {}",val) } else { *val })
+        .collect::<Vec<_>>()
+    }));
+    let payload = res.unwrap_err();
+    // The original payload is re-raised, not a generic message
+    assert_eq!(payload.downcast_ref::<String>().map(String::as_str),Some("failed on value 5000"));
+}
+
+#[test]
+fn for_each_panic_stops_early_test() {
+    let processed = AtomicUsize::new(0);
+    let vec = (0..10_000).collect::<Vec<usize>>();
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        vec.parallel_iter()
+        .for_each(|_| {
+            processed.fetch_add(1, Ordering::Relaxed);
+            std::thread::sleep(std::time::Duration::from_micros(50));
+            panic!("stop");
+        })
+    }));
+    assert_eq!(res.unwrap_err().downcast_ref::<&str>(),Some(&"stop"));
+    assert!(processed.load(Ordering::Relaxed) < 5_000);
+}
+
+#[test]
+fn worker_panic_error_test() {
+    let vec = (0..1_000).collect::<Vec<i32>>();
+    let res = WorkerController::new(
+        |val:&i32| if *val == 500 { panic!("bad value") } else { *val },
+        vec.parallel_iter(),
+        ThreadPrioritization::Remaining)
+    .run::<Vec<_>>();
+
+    match res {
+        Err(WorkThreadError::WorkerPanic { worker, index, payload }) => {
+            assert!(!worker.is_empty());
+            assert_eq!(index,Some(500));
+            assert_eq!(payload.downcast_ref::<&str>(),Some(&"bad value"));
+        }
+        _ => panic!("expected the worker panic to be returned")
+    }
+}
+
+#[test]
+fn pool_usable_after_panic_test() {
+    let pool = ThreadPool::new(2);
+    let vec = (0..1_000).collect::<Vec<i32>>();
+    for _ in 0..5 {
+        let res = catch_unwind(AssertUnwindSafe(|| {
+            vec.parallel_iter().map(|val| if *val == 10 { panic!("fail") } else { *val }).pool(&pool).collect::<Vec<_>>()
+        }));
+        assert!(res.is_err());
+    }
+    let res = vec.parallel_iter().map(|val| val + 1).pool(&pool).collect::<Vec<_>>();
+    assert_eq!(res,(1..1_001).collect::<Vec<i32>>());
+}
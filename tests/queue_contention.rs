@@ -23,7 +23,7 @@ fn queue_contention() {
                 }
 
                 if let Some(values )= primary.steal() {
-                    res.extend(values.into_iter());
+                    res.extend(values);
                 }  
                 res              
             }
@@ -58,4 +58,199 @@ fn queue_contention() {
 
     });
 
-}
\ No newline at end of file
+}
+
+/// Tests for queue contention where both accessors start together and yield now and then, so that they interleave
+/// even on a single core
+#[test]
+fn queue_contention_interleaved() {
+    let values = (0..100_000).collect::<Vec<_>>();
+
+    let (mut primary, secondary) = LimitAccessQueue::<i32,Coordination>::new();
+    _ = primary.write(values);
+
+    // Both threads start contending together, else one may drain the queue before the other is spawned
+    let start = std::sync::Barrier::new(2);
+    let start = &start;
+
+    std::thread::scope(move |s| {
+        let handle1 = s.spawn(move ||
+            {           
+                start.wait();
+                let mut res = Vec::new();
+                for idx in 0..40_000 {                                         
+                    // yield once in a while so that the accessors interleave even on a single core
+                    if idx % 1_000 == 0 { std::thread::yield_now(); }
+                    if let Some(value ) = primary.pop() {                                                
+                        res.push(value);
+                    } else {
+                        break;
+                    }                                        
+                }
+
+                if let Some(values )= primary.steal() {
+                    res.extend(values);
+                }  
+                res              
+            }
+        ); 
+
+
+        let handle2 = s.spawn(
+            move || {
+                start.wait();
+                let mut res = Vec::new();
+                for idx in 0..40_000 {                                                        
+                    if idx % 1_000 == 0 { std::thread::yield_now(); }
+                    if let Some(values ) = secondary.pop() {                                                            
+                        res.push(values);
+                    } else {
+                        break;
+                    }
+                    
+                }
+                res
+            }
+        );    
+
+        let results1 = handle1.join().unwrap();
+        let results2 = handle2.join().unwrap();
+
+        // assert that secondary at least got some values and not just primary. Allowing two queues act in parallel
+        // 100,000 is a large enough number to give enough opportunities for both accessors
+        assert!(!results1.is_empty());
+        assert!(!results2.is_empty());
+
+        // assert that sum of both the vectors is 1000
+        assert_eq!(results1.len() + results2.len(), 100_000);
+
+    });
+
+}
+
+/// Tests that the values are taken exactly once when the primary steals halves while the secondary pops
+#[test]
+fn steal_half_contention() {
+    for _ in 0..20 {
+        let (mut primary, secondary) = LimitAccessQueue::<usize,Coordination>::new();
+        _ = primary.write((0..50_000).collect::<Vec<usize>>());
+
+        let start = std::sync::Barrier::new(2);
+        let start = &start;
+
+        let (stolen, popped) = std::thread::scope(move |s| {
+            let stealer = s.spawn(move || {
+                start.wait();
+                let mut res = Vec::new();
+                while let Some(values) = primary.steal_half() {
+                    res.extend(values);
+                }
+                res
+            });
+            let popper = s.spawn(move || {
+                start.wait();
+                std::iter::from_fn(|| secondary.pop()).collect::<Vec<usize>>()
+            });
+            (stealer.join().unwrap(), popper.join().unwrap())
+        });
+
+        // The secondary pops the values written last first, and the primary steals those written first
+        assert!(popped.windows(2).all(|pair| pair[0] > pair[1]));
+        assert!(stolen.windows(2).all(|pair| pair[0] < pair[1]));
+        let mut all = stolen;
+        all.extend(popped);
+        all.sort();
+        assert_eq!(all,(0..50_000).collect::<Vec<usize>>());
+    }
+}
+
+/// Tests that values left in the queue, including those moved when the queue grows, are dropped once
+#[test]
+fn queue_drop() {
+    let counter = std::sync::Arc::new(());
+    let (primary, secondary) = LimitAccessQueue::<std::sync::Arc<()>,Coordination>::new();
+    for _ in 0..10 {
+        _ = primary.write((0..100).map(|_| counter.clone()).collect::<Vec<_>>());
+    }
+    assert_eq!(primary.len(),1_000);
+    drop(secondary.pop_count(300));
+    drop(primary.pop_count(200));
+    assert_eq!(std::sync::Arc::strong_count(&counter),501);
+    drop(primary);
+    drop(secondary);
+    assert_eq!(std::sync::Arc::strong_count(&counter),1);
+}
+
+/// Tests the state shared by the accessors
+#[test]
+fn queue_state() {
+    let (mut primary, mut secondary) = LimitAccessQueue::<i32,Coordination>::new();
+    assert_eq!(secondary.state(),Coordination::Waiting);
+    primary.set_state(Coordination::Run);
+    assert_eq!(secondary.compare_exchange_state(Coordination::Run, Coordination::Waiting),Ok(Coordination::Run));
+    assert_eq!(primary.compare_exchange_state(Coordination::Run, Coordination::Done),Err(Coordination::Waiting));
+    assert_eq!(primary.state(),Coordination::Waiting);
+}
+
+/// Tests that values handed over by the primary are each popped or stolen once, with the primary stealing while the
+/// secondary pops and only writing once the secondary is waiting again
+#[test]
+fn write_pop_contention() {
+    let (mut primary, mut secondary) = LimitAccessQueue::<usize,Coordination>::new();
+
+    let (popped, stolen) = std::thread::scope(move |s| {
+        let popper = s.spawn(move || {
+            let mut res = Vec::new();
+            loop {
+                match secondary.state() {
+                    Coordination::Run => {
+                        res.extend(std::iter::from_fn(|| secondary.pop()));
+                        _ = secondary.compare_exchange_state(Coordination::Run, Coordination::Waiting);
+                    },
+                    Coordination::Done => break,
+                    _ => std::thread::yield_now()
+                }
+            }
+            res
+        });
+        let mut stolen = Vec::new();
+        // Batches of uneven length make the queue grow between runs
+        for batch in 0..2_000 {
+            while primary.state() != Coordination::Waiting {
+                if let Some(value) = primary.pop() {
+                    stolen.push(value);
+                }
+            }
+            let values = (batch * 100..batch * 100 + 1 + batch % 100).collect::<Vec<usize>>();
+            assert_eq!(primary.write(values),Ok(true));
+            primary.set_state(Coordination::Run);
+        }
+        while primary.state() != Coordination::Waiting {
+            std::thread::yield_now();
+        }
+        primary.set_state(Coordination::Done);
+        (popper.join().unwrap(), stolen)
+    });
+
+    let mut all = popped.into_iter().chain(stolen).collect::<Vec<usize>>();
+    all.sort();
+    let expected = (0..2_000).flat_map(|batch| batch * 100..batch * 100 + 1 + batch % 100).collect::<Vec<usize>>();
+    assert_eq!(all,expected);
+}
+
+/// Tests that only the primary writes, and only while the runner is waiting
+#[test]
+fn write_access() {
+    let (mut primary, secondary) = LimitAccessQueue::<i32,Coordination>::new();
+    assert_eq!(secondary.write(vec![1, 2, 3]),Err(false));
+    assert_eq!(secondary.replace(vec![1, 2, 3]),Err(false));
+    assert!(primary.is_empty());
+    primary.set_state(Coordination::Run);
+    assert_eq!(primary.write(vec![1, 2, 3]),Err(false));
+    assert_eq!(primary.replace(vec![1, 2, 3]),Err(false));
+    assert!(primary.is_empty());
+    primary.set_state(Coordination::Waiting);
+    assert_eq!(primary.write(vec![1, 2, 3]),Ok(true));
+    assert_eq!(primary.replace(vec![4, 5]),Ok(true));
+    assert_eq!(secondary.pop_count(10),Some(vec![5, 4]));
+}
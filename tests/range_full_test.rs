@@ -0,0 +1,97 @@
+use parallel_task::prelude::*;
+use parallel_task::iterators::{iterator::ParallelIterator, ranges::RangeQueue};
+
+#[test]
+fn empty_and_reversed_range_test() {
+    assert_eq!((5..5).into_parallel_iter().count(),0);
+    #[allow(clippy::reversed_empty_ranges)]
+    let reversed = (10..3).into_parallel_iter().count();
+    assert_eq!(reversed,0);
+    #[allow(clippy::reversed_empty_ranges)]
+    let reversed = (10..=3u8).into_parallel_iter().count();
+    assert_eq!(reversed,0);
+    assert_eq!((7..=7).into_parallel_iter().map(|val| val).collect::<Vec<i32>>(),vec![7]);
+}
+
+#[test]
+fn inclusive_range_test() {
+    let res = (0..=u8::MAX).into_parallel_iter().map(|val| val).collect::<Vec<u8>>();
+    assert_eq!(res,(0..=u8::MAX).collect::<Vec<u8>>());
+    let res = (i16::MIN..=i16::MAX).into_parallel_iter().map(|val| val as i64).sum::<i64>();
+    assert_eq!(res,(i16::MIN..=i16::MAX).map(|val| val as i64).sum::<i64>());
+    assert_eq!((1..=10_000u64).into_parallel_iter().sum::<u64>(),50_005_000);
+}
+
+#[test]
+fn integer_width_test() {
+    assert_eq!((-100..100i8).into_parallel_iter().map(|val| val).collect::<Vec<i8>>(),(-100..100i8).collect::<Vec<i8>>());
+    assert_eq!((0..1_000u16).into_parallel_iter().count(),1_000);
+    assert_eq!((-5..5isize).into_parallel_iter().map(|val| val * 2).collect::<Vec<isize>>(),(-5..5isize).map(|val| val * 2).collect::<Vec<isize>>());
+    let start = u128::MAX - 1_000;
+    assert_eq!((start..=u128::MAX).into_parallel_iter().map(|val| val).collect::<Vec<u128>>(),(start..=u128::MAX).collect::<Vec<u128>>());
+    assert_eq!((i128::MIN..i128::MIN + 500).into_parallel_iter().map(|val| val).collect::<Vec<i128>>(),(i128::MIN..i128::MIN + 500).collect::<Vec<i128>>());
+}
+
+#[test]
+fn char_range_test() {
+    assert_eq!(('a'..='z').into_parallel_iter().map(|val| val).collect::<Vec<char>>(),('a'..='z').collect::<Vec<char>>());
+    // Range spans the surrogate code points, which are not chars
+    let (start, end) = ('\u{D000}', '\u{E800}');
+    assert_eq!((start..end).into_parallel_iter().map(|val| val).collect::<Vec<char>>(),(start..end).collect::<Vec<char>>());
+    assert_eq!((start..=end).into_parallel_iter().count(),(start..=end).count());
+    assert_eq!(('\u{D7FF}'..'\u{E000}').into_parallel_iter().map(|val| val).collect::<Vec<char>>(),vec!['\u{D7FF}']);
+}
+
+#[test]
+fn exhausted_inclusive_range_test() {
+    let mut range = 0..=3;
+    range.by_ref().for_each(drop);
+    assert_eq!(range.into_parallel_iter().count(),0);
+}
+
+#[test]
+fn max_boundary_range_test() {
+    let res = (u64::MAX - 5..=u64::MAX).into_parallel_iter().map(|val| val).collect::<Vec<u64>>();
+    assert_eq!(res,(u64::MAX - 5..=u64::MAX).collect::<Vec<u64>>());
+    let res = (i64::MAX - 3..=i64::MAX).into_parallel_iter().map(|val| val).collect::<Vec<i64>>();
+    assert_eq!(res,(i64::MAX - 3..=i64::MAX).collect::<Vec<i64>>());
+    // The longest ranges hold usize::MAX values, which are narrowed rather than pulled
+    assert_eq!((0..usize::MAX).into_parallel_iter().len(),Some(usize::MAX));
+    assert_eq!((1..=usize::MAX).into_parallel_iter().skip(usize::MAX - 2).map(|val| val).collect::<Vec<usize>>(),vec![usize::MAX - 1, usize::MAX]);
+    assert_eq!((i64::MIN..i64::MAX).into_parallel_iter().skip(usize::MAX - 2).map(|val| val).collect::<Vec<i64>>(),vec![i64::MAX - 2, i64::MAX - 1]);
+    let start = i128::MIN;
+    let end = start + usize::MAX as i128;
+    assert_eq!((start..end).into_parallel_iter().take(2).map(|val| val).collect::<Vec<i128>>(),vec![start, start + 1]);
+    assert_eq!((u128::MAX - 2..=u128::MAX).into_parallel_iter().map(|val| val).collect::<Vec<u128>>(),vec![u128::MAX - 2, u128::MAX - 1, u128::MAX]);
+}
+
+#[test]
+fn too_long_range_test() {
+    assert!(RangeQueue::try_inclusive(0, usize::MAX).is_none());
+    assert!(RangeQueue::try_inclusive(0, u64::MAX).is_none());
+    assert!(RangeQueue::try_inclusive(i64::MIN, i64::MAX).is_none());
+    assert!(RangeQueue::try_exclusive(0, u128::MAX).is_none());
+    assert!(RangeQueue::try_exclusive(i128::MIN, i128::MAX).is_none());
+    assert!(RangeQueue::try_inclusive(char::MIN, char::MAX).is_some());
+    let queue = RangeQueue::try_inclusive(0, usize::MAX - 1).unwrap();
+    assert_eq!(ParallelIterator::new(queue).len(),Some(usize::MAX));
+}
+
+#[test]
+#[should_panic(expected = "range is too long to be iterated in parallel")]
+fn too_long_inclusive_range_test() {
+    _ = (0..=usize::MAX).into_parallel_iter();
+}
+
+#[test]
+#[should_panic(expected = "range is too long to be iterated in parallel")]
+fn too_long_i128_range_test() {
+    _ = (i128::MIN..i128::MAX).into_parallel_iter();
+}
+
+#[test]
+fn long_range_search_test() {
+    // The range is far too long to be pulled in one go, so it is pulled in batches till the value is found
+    assert_eq!((0..u64::MAX).into_parallel_iter().find_first(|val| *val == 100_000),Some(100_000));
+    assert!((0..=usize::MAX - 1).into_parallel_iter().any(|val| val == 5_000));
+}
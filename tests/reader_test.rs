@@ -0,0 +1,118 @@
+use std::io::{BufRead, Cursor, Write};
+use parallel_task::prelude::*;
+use parallel_task::iterators::reader::ReadRegions;
+
+fn lines_with_region_size(text:&str, region_size:u64) -> Vec<String> {
+    ReadRegions::new(Cursor::new(text.as_bytes().to_vec())).unwrap()
+    .with_region_size(region_size)
+    .lines()
+    .map(|line| line.unwrap())
+    .collect::<Vec<String>>()
+}
+
+#[test]
+fn lines_match_serial_test() {
+    let text = (0..10_000).map(|val| "x".repeat(val % 37)).collect::<Vec<String>>().join("\n");
+    let serial = text.lines().map(|line| line.to_owned()).collect::<Vec<String>>();
+    // Region boundaries fall before, on and after the newlines
+    for region_size in [1, 2, 7, 36, 37, 38, 1_000, 1 << 20] {
+        assert_eq!(lines_with_region_size(&text, region_size),serial);
+    }
+}
+
+#[test]
+fn edge_lines_test() {
+    assert_eq!(lines_with_region_size("", 4),Vec::<String>::new());
+    assert_eq!(lines_with_region_size("\n", 1),vec![""]);
+    assert_eq!(lines_with_region_size("a\n\nb\n", 1),vec!["a","","b"]);
+    assert_eq!(lines_with_region_size("a\r\nb\r\n", 3),vec!["a","b"]);
+    // A line longer than many regions is read once by the region it starts in
+    let long = format!("short\n{}\nend", "y".repeat(500));
+    assert_eq!(lines_with_region_size(&long, 16),vec!["short".to_owned(),"y".repeat(500),"end".to_owned()]);
+}
+
+#[test]
+fn records_test() {
+    let data = b"alpha\0beta\0\0gamma".to_vec();
+    let res = ReadRegions::new(Cursor::new(data)).unwrap()
+    .with_delimiter(0)
+    .with_region_size(3)
+    .records()
+    .map(|record| String::from_utf8(record.unwrap()).unwrap())
+    .collect::<Vec<String>>();
+    assert_eq!(res,vec!["alpha","beta","","gamma"]);
+    let res = Cursor::new(b"1;2;3".to_vec()).parallel_records(b';').unwrap().map(|record| record.unwrap()).collect::<Vec<Vec<u8>>>();
+    assert_eq!(res,vec![b"1".to_vec(),b"2".to_vec(),b"3".to_vec()]);
+}
+
+#[test]
+fn invalid_utf8_test() {
+    let res = Cursor::new(b"ok\n\xff\xfe\nok".to_vec()).parallel_lines().unwrap().map(|line| line.is_ok()).collect::<Vec<bool>>();
+    assert_eq!(res,vec![true,false,true]);
+}
+
+#[test]
+fn file_lines_test() {
+    let path = std::env::temp_dir().join(format!("parallel_task_reader_test_{}.log", std::process::id()));
+    let mut file = std::fs::File::create(&path).unwrap();
+    for val in 0..50_000 {
+        writeln!(file, "{} level={}", val, if val % 10 == 0 { "error" } else { "info" }).unwrap();
+    }
+    drop(file);
+
+    let errors = ReadRegions::new(std::fs::File::open(&path).unwrap()).unwrap()
+    .with_region_size(4_096)
+    .lines()
+    .filter(|line| line.as_ref().is_ok_and(|line| line.ends_with("error")))
+    .count();
+    assert_eq!(errors,5_000);
+
+    let serial = std::io::BufReader::new(std::fs::File::open(&path).unwrap()).lines().map(|line| line.unwrap()).collect::<Vec<String>>();
+    let res = std::fs::File::open(&path).unwrap().parallel_lines().unwrap().map(|line| line.unwrap()).collect::<Vec<String>>();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(res,serial);
+}
+
+#[test]
+fn file_at_offsets_test() {
+    let path = std::env::temp_dir().join(format!("parallel_task_reader_offsets_test_{}.log", std::process::id()));
+    let mut file = std::fs::File::create(&path).unwrap();
+    for val in 0..50_000 {
+        writeln!(file, "{} level={}", val, if val % 10 == 0 { "error" } else { "info" }).unwrap();
+    }
+    drop(file);
+
+    let serial = std::io::BufReader::new(std::fs::File::open(&path).unwrap()).lines().map(|line| line.unwrap()).collect::<Vec<String>>();
+    for region_size in [7, 4_096, 1 << 20] {
+        let res = ReadRegions::from_file(std::fs::File::open(&path).unwrap()).unwrap()
+        .with_region_size(region_size)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<String>>();
+        assert_eq!(res,serial);
+    }
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn opener_test() {
+    use std::sync::{Arc, atomic::{AtomicUsize, Ordering}};
+    let text = (0..20_000).map(|val| val.to_string()).collect::<Vec<String>>().join("\n");
+    let data:Arc<[u8]> = Arc::from(text.as_bytes());
+    let opened = Arc::new(AtomicUsize::new(0));
+    let counter = opened.clone();
+    let res = ReadRegions::from_opener(move || {
+        counter.fetch_add(1, Ordering::Relaxed);
+        Ok(Cursor::new(data.clone()))
+    }).unwrap()
+    .with_region_size(64)
+    .lines()
+    .map(|line| line.unwrap().parse::<u64>().unwrap())
+    .collect::<Vec<u64>>();
+    assert_eq!(res,(0..20_000).collect::<Vec<u64>>());
+    // Once for the length, then at most once per worker thread rather than once per region
+    assert!(opened.load(Ordering::Relaxed) <= 1 + ThreadPool::global().max_threads());
+
+    let err = ReadRegions::<Cursor<Vec<u8>>>::from_opener(|| Err(std::io::Error::other("no reader")));
+    assert!(err.is_err());
+}
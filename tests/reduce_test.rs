@@ -0,0 +1,74 @@
+use parallel_task::prelude::*;
+
+#[test]
+fn sum_product_test() {
+    let vec = (0..100_000).collect::<Vec<u64>>();
+    assert_eq!(vec.parallel_iter().sum::<u64>(),vec.iter().sum::<u64>());
+    assert_eq!(vec.parallel_iter().map(|val| val * 3).sum::<u64>(),vec.iter().map(|val| val * 3).sum::<u64>());
+    let small = (1..=20u64).collect::<Vec<_>>();
+    assert_eq!(small.parallel_iter().product::<u64>(),small.iter().product::<u64>());
+}
+
+#[test]
+fn empty_reduction_test() {
+    let vec:Vec<i32> = Vec::new();
+    assert_eq!(vec.parallel_iter().sum::<i32>(),0);
+    assert_eq!(vec.parallel_iter().min(),None);
+    assert_eq!(vec.parallel_iter().count(),0);
+    let res = (0..1_000).collect::<Vec<i32>>().parallel_iter().filter(|val| **val < 0).map(|val| *val).reduce(|| 0,|a,b| a + b);
+    assert_eq!(res,0);
+}
+
+#[test]
+fn ordered_reduce_test() {
+    // Concatenation is associative but not commutative, so the accumulators must be combined in order. Uneven job
+    // lengths force the controller to redistribute the queues across threads.
+    let vec = (0..5_000u64).collect::<Vec<_>>();
+    let res = vec.parallel_iter()
+    .map(|val| {
+        if val % 11 == 0 { std::thread::sleep(std::time::Duration::from_micros(50)); }
+        format!("{},",val)
+    })
+    .reduce(String::new,|a,b| a + &b);
+    let test = vec.iter().map(|val| format!("{},",val)).collect::<String>();
+    assert_eq!(res,test);
+}
+
+#[test]
+fn fold_test() {
+    let vec = (0..10_000).map(|val| val.to_string()).collect::<Vec<String>>();
+    let res = vec.parallel_iter().fold(|| 0usize,|acc,val| acc + val.len(),|a,b| a + b);
+    assert_eq!(res,vec.iter().map(|val| val.len()).sum::<usize>());
+}
+
+#[test]
+fn min_max_test() {
+    let vec = (0..100_000).map(|val| (val * 7_919) % 100_003).collect::<Vec<i64>>();
+    assert_eq!(vec.parallel_iter().min(),vec.iter().min());
+    assert_eq!(vec.parallel_iter().max(),vec.iter().max());
+    assert_eq!(vec.parallel_iter().map(|val| *val).max(),vec.iter().copied().max());
+}
+
+#[test]
+fn min_max_by_key_ties_test() {
+    // The keys repeat, so the ties must be settled as with the Iterator functions
+    let vec = (0..50_000usize).collect::<Vec<_>>();
+    assert_eq!(vec.parallel_iter().min_by_key(|val| **val % 100),vec.iter().min_by_key(|val| **val % 100));
+    assert_eq!(vec.parallel_iter().max_by_key(|val| **val % 100),vec.iter().max_by_key(|val| **val % 100));
+    assert_eq!(vec.parallel_iter().map(|val| *val).min_by_key(|val| val % 100),Some(0));
+    assert_eq!(vec.parallel_iter().map(|val| *val).max_by_key(|val| val % 100),Some(49_999));
+}
+
+#[test]
+fn count_test() {
+    let vec = (0..100_000).collect::<Vec<i32>>();
+    assert_eq!(vec.parallel_iter().count(),100_000);
+    assert_eq!(vec.parallel_iter().filter(|val| **val % 3 == 0).count(),vec.iter().filter(|val| **val % 3 == 0).count());
+    assert_eq!(vec.parallel_iter().flat_map(|val| 0..(*val % 3)).count(),vec.iter().flat_map(|val| 0..(*val % 3)).count());
+}
+
+#[test]
+fn range_reduction_test() {
+    assert_eq!((0..100_000u64).into_parallel_iter().sum::<u64>(),(0..100_000u64).sum::<u64>());
+    assert_eq!((0..100_000i64).into_parallel_iter().map(|val| val - 50_000).min(),Some(-50_000));
+}
@@ -0,0 +1,70 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use parallel_task::prelude::*;
+
+#[test]
+fn any_all_test() {
+    let vec = (0..100_000).collect::<Vec<i32>>();
+    assert!(vec.parallel_iter().any(|val| *val == 99_999));
+    assert!(!vec.parallel_iter().any(|val| *val < 0));
+    assert!(vec.parallel_iter().all(|val| *val >= 0));
+    assert!(!vec.parallel_iter().all(|val| *val < 50_000));
+    let empty:Vec<i32> = Vec::new();
+    assert!(!empty.parallel_iter().any(|_| true));
+    assert!(empty.parallel_iter().all(|_| false));
+}
+
+#[test]
+fn any_stops_early_test() {
+    let processed = AtomicUsize::new(0);
+    let vec = (0..1_000_000).collect::<Vec<u64>>();
+    let found = vec.parallel_iter().any(|val| {
+        processed.fetch_add(1, Ordering::Relaxed);
+        *val == 100
+    });
+    assert!(found);
+    assert!(processed.load(Ordering::Relaxed) < vec.len());
+}
+
+#[test]
+fn find_first_test() {
+    // Matches are spread across the source, and uneven job lengths force the queues to be redistributed
+    let vec = (0..20_000u64).collect::<Vec<_>>();
+    let res = vec.parallel_iter()
+    .map(|val| {
+        if val % 13 == 0 { std::thread::sleep(std::time::Duration::from_micros(20)); }
+        *val
+    })
+    .find_first(|val| val % 997 == 996);
+    assert_eq!(res,vec.iter().copied().find(|val| val % 997 == 996));
+    assert_eq!(vec.parallel_iter().find_first(|val| **val > 19_000),Some(&19_001));
+    assert_eq!(vec.parallel_iter().find_first(|val| **val > 20_000),None);
+}
+
+#[test]
+fn find_first_after_flat_map_test() {
+    let vec = (0..5_000usize).collect::<Vec<_>>();
+    let res = vec.parallel_iter().flat_map(|val| (0..(*val % 4)).map(move |sub| (*val, sub))).find_first(|(_,sub)| *sub == 2);
+    assert_eq!(res,Some((3,2)));
+}
+
+#[test]
+fn find_any_test() {
+    let vec = (0..100_000).collect::<Vec<i32>>();
+    let res = vec.parallel_iter().find_any(|val| **val % 10_000 == 1);
+    assert!(res.is_some_and(|val| val % 10_000 == 1));
+    assert_eq!(vec.parallel_iter().find_any(|val| **val < 0),None);
+}
+
+#[test]
+fn position_any_test() {
+    let vec = (0..100_000).map(|val| val.to_string()).collect::<Vec<String>>();
+    assert_eq!(vec.parallel_iter().position_any(|val| val == "54321"),Some(54_321));
+    assert_eq!(vec.parallel_iter().position_any(|val| val.is_empty()),None);
+    // The position is among the outputs of the pipeline, as with a serial position
+    let pos = vec.parallel_iter().filter(|val| val.len() > 3).position_any(|val| val.ends_with("000"));
+    assert_eq!(pos,vec.iter().filter(|val| val.len() > 3).position(|val| val.ends_with("000")));
+    let nums = (0..100_000u64).collect::<Vec<u64>>();
+    assert_eq!(nums.parallel_iter().filter(|val| **val % 2 == 0).position_any(|val| *val == 10),Some(5));
+    let pos = nums.parallel_iter().flat_map(|val| vec![*val; (*val % 3) as usize]).position_any(|val| val == 60_001);
+    assert_eq!(pos,nums.iter().flat_map(|val| vec![*val; (*val % 3) as usize]).position(|val| val == 60_001));
+}
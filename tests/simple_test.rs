@@ -5,7 +5,7 @@ use rayon::prelude::*;
 #[test]
 fn simple_test() {
        
-    let vec = (0..1_000_000).into_iter().collect::<Vec<_>>();
+    let vec = (0..1_000_000).collect::<Vec<_>>();
     let tm = std::time::Instant::now();
     println!("PT vec: {}",tm.elapsed().as_micros()); 
     let iter = vec.into_parallel_iter();
@@ -15,7 +15,7 @@ fn simple_test() {
     let coll = map.collect::<Vec<_>>();  
     println!("PT collect: {}",tm.elapsed().as_micros());  
 
-    let vec = (0..1_000_000).into_iter().collect::<Vec<_>>();
+    let vec = (0..1_000_000).collect::<Vec<_>>();
     let tm = std::time::Instant::now();
     vec.into_par_iter().map(|v|v+100i32).collect::<Vec<_>>();  
     println!("Rayon: {}",tm.elapsed().as_micros());  
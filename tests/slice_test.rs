@@ -0,0 +1,45 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+use parallel_task::prelude::*;
+
+#[test]
+fn borrowed_slice_test() {
+    let buffer = (0..100_000).collect::<Vec<i32>>();
+    let view:&[i32] = &buffer[25_000..75_000];
+    let res = view.parallel_iter().map(|val| val * 2).collect::<Vec<i32>>();
+    assert_eq!(res,view.iter().map(|val| val * 2).collect::<Vec<i32>>());
+    assert_eq!(buffer[..10].parallel_iter().sum::<i32>(),45);
+}
+
+#[test]
+fn array_test() {
+    let arr = [3, 1, 4, 1, 5, 9, 2, 6];
+    assert_eq!(arr.parallel_iter().map(|val| *val).collect::<Vec<i32>>(),arr.to_vec());
+    let strings = ["a".to_owned(), "b".to_owned(), "c".to_owned()];
+    let res = strings.into_parallel_iter().map(|val| val + "!").collect::<Vec<String>>();
+    assert_eq!(res,vec!["a!","b!","c!"]);
+}
+
+#[test]
+fn boxed_slice_test() {
+    let boxed:Box<[u64]> = (0..10_000).collect::<Vec<u64>>().into_boxed_slice();
+    assert_eq!(boxed.parallel_iter().map(|val| *val).collect::<Vec<u64>>(),boxed.to_vec());
+    let res = boxed.into_parallel_iter().map(|val| val + 1).collect::<Vec<u64>>();
+    assert_eq!(res,(1..10_001).collect::<Vec<u64>>());
+}
+
+#[test]
+fn arc_slice_test() {
+    let shared:Arc<[String]> = (0..10_000).map(|val| val.to_string()).collect::<Vec<_>>().into();
+    let res = shared.parallel_iter().map(|val| val.len()).collect::<Vec<usize>>();
+    assert_eq!(res,shared.iter().map(|val| val.len()).collect::<Vec<usize>>());
+}
+
+#[test]
+fn cow_slice_test() {
+    let vec = (0..10_000).collect::<Vec<i64>>();
+    let borrowed:Cow<[i64]> = Cow::Borrowed(&vec);
+    assert_eq!(borrowed.parallel_iter().map(|val| *val).collect::<Vec<i64>>(),vec);
+    let owned:Cow<[i64]> = Cow::Owned(vec.clone());
+    assert_eq!(owned.parallel_iter().filter(|val| **val % 2 == 0).count(),5_000);
+}
@@ -1,4 +1,5 @@
 use parallel_task::{map::ParallelMapIter, prelude::IntoParallelIter};
+use parallel_task::prelude::{ParallelIter, ParallelStr};
 
 
 #[test]
@@ -11,3 +12,63 @@ fn string_test() {
 
     assert_eq!(vec.len(),vec2.len());
 }
+
+fn sample() -> String {
+    // Multi-byte chars make sure the pieces are cut at char boundaries
+    (0..20_000).map(|val| match val % 5 {
+        0 => "héllo,".to_owned(),
+        1 => "wörld\n".to_owned(),
+        2 => "日本語,,".to_owned(),
+        3 => "\r\n".to_owned(),
+        _ => format!("{}🦀", val)
+    }).collect::<String>()
+}
+
+#[test]
+fn chars_test() {
+    let text = sample();
+    let res = text.parallel_chars().map(|ch| ch).collect::<String>();
+    assert_eq!(res,text);
+    let res = text.parallel_chars().filter(|ch| !ch.is_ascii()).count();
+    assert_eq!(res,text.chars().filter(|ch| !ch.is_ascii()).count());
+}
+
+#[test]
+fn bytes_test() {
+    let text = sample();
+    let res = text.parallel_bytes().map(|byte| byte).collect::<Vec<u8>>();
+    assert_eq!(res,text.as_bytes());
+    assert_eq!(text.parallel_bytes().map(|byte| byte as u64).sum::<u64>(),text.bytes().map(|byte| byte as u64).sum::<u64>());
+}
+
+#[test]
+fn lines_test() {
+    let text = sample();
+    let res = text.parallel_lines().map(|line| line.to_owned()).collect::<Vec<String>>();
+    assert_eq!(res,text.lines().map(|line| line.to_owned()).collect::<Vec<String>>());
+    for text in ["", "\n", "a", "a\n", "a\n\n", "\nb", "a\r\nb\r\n"] {
+        assert_eq!(text.parallel_lines().map(|line| line).collect::<Vec<&str>>(),text.lines().collect::<Vec<&str>>());
+    }
+}
+
+#[test]
+fn split_test() {
+    let text = sample();
+    let res = text.parallel_split(',').map(|val| val).collect::<Vec<&str>>();
+    assert_eq!(res,text.split(',').collect::<Vec<&str>>());
+    let res = text.parallel_split(|ch:char| ch.is_whitespace()).map(|val| val.len()).collect::<Vec<usize>>();
+    assert_eq!(res,text.split(|ch:char| ch.is_whitespace()).map(|val| val.len()).collect::<Vec<usize>>());
+    for text in ["", ",", "a,", ",a", "a,,b", "a,b,"] {
+        assert_eq!(text.parallel_split(',').map(|val| val).collect::<Vec<&str>>(),text.split(',').collect::<Vec<&str>>());
+    }
+}
+
+#[test]
+fn string_collect_test() {
+    let words = vec!["alpha", "beta", "gamma"];
+    assert_eq!(words.parallel_iter().map(|word| word.to_uppercase()).collect::<String>(),"ALPHABETAGAMMA");
+    assert_eq!(words.parallel_iter().map(|word| *word).collect::<String>(),"alphabetagamma");
+    let text = sample();
+    let res = text.parallel_lines().flat_map(|line| line.chars().rev().collect::<Vec<char>>()).collect::<String>();
+    assert_eq!(res,text.lines().flat_map(|line| line.chars().rev().collect::<Vec<char>>()).collect::<String>());
+}
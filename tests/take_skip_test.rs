@@ -0,0 +1,93 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use parallel_task::prelude::*;
+
+#[test]
+fn take_test() {
+    let vec = (0..100_000).collect::<Vec<u64>>();
+    assert_eq!(vec.parallel_iter().take(1_000).map(|val| *val).collect::<Vec<u64>>(),(0..1_000).collect::<Vec<u64>>());
+    assert_eq!(vec.parallel_iter().take(200_000).count(),100_000);
+    assert_eq!(vec.parallel_iter().take(0).count(),0);
+    // An endless source stops being pulled once enough values are taken
+    assert_eq!((0u64..).par_bridge().take(5_000).sum::<u64>(),(0..5_000).sum::<u64>());
+}
+
+#[test]
+fn skip_test() {
+    let vec = (0..100_000).collect::<Vec<u64>>();
+    assert_eq!(vec.clone().into_parallel_iter().skip(99_000).map(|val| val).collect::<Vec<u64>>(),(99_000..100_000).collect::<Vec<u64>>());
+    assert_eq!(vec.parallel_iter().skip(100_000).count(),0);
+    assert_eq!(vec.parallel_iter().skip(5).take(3).map(|val| *val).collect::<Vec<u64>>(),vec![5,6,7]);
+    assert_eq!((0..10_000).filter(|val| val % 2 == 0).par_bridge().skip(10).take(2).map(|val| val).collect::<Vec<i32>>(),vec![20,22]);
+}
+
+#[test]
+fn step_by_test() {
+    let vec = (0..100_000).collect::<Vec<u32>>();
+    let res = vec.parallel_iter().step_by(7).map(|val| *val).collect::<Vec<u32>>();
+    assert_eq!(res,vec.iter().step_by(7).copied().collect::<Vec<u32>>());
+    let res = (0..=255u8).into_parallel_iter().skip(3).step_by(50).map(|val| val).collect::<Vec<u8>>();
+    assert_eq!(res,vec![3,53,103,153,203,253]);
+    // Values pulled over several batches keep their step
+    let res = (0..10_000).par_bridge().step_by(3).map(|val| val).collect::<Vec<i32>>();
+    assert_eq!(res,(0..10_000).step_by(3).collect::<Vec<i32>>());
+}
+
+#[test]
+fn adjusted_len_test() {
+    let vec = (0..1_000).collect::<Vec<i32>>();
+    assert_eq!(vec.parallel_iter().take(10).len(),Some(10));
+    assert_eq!(vec.parallel_iter().skip(990).len(),Some(10));
+    assert_eq!(vec.parallel_iter().skip(2_000).len(),Some(0));
+    assert_eq!(vec.parallel_iter().step_by(3).len(),Some(334));
+    assert_eq!(vec.parallel_iter().skip(1).step_by(3).len(),Some(333));
+    let mut out = Vec::new();
+    vec.parallel_iter().step_by(10).map(|val| val * 2).collect_into_vec(&mut out);
+    assert_eq!(out,(0..1_000).step_by(10).map(|val| val * 2).collect::<Vec<i32>>());
+}
+
+#[test]
+#[should_panic(expected = "step must not be zero")]
+fn step_by_zero_test() {
+    let vec = vec![1, 2, 3];
+    _ = vec.parallel_iter().step_by(0);
+}
+
+#[test]
+fn take_while_any_test() {
+    let processed = AtomicUsize::new(0);
+    let vec = (0..1_000_000).collect::<Vec<u64>>();
+    let res = vec.parallel_iter().take_while_any(|val| {
+        processed.fetch_add(1, Ordering::Relaxed);
+        **val < 1_000
+    }).map(|val| *val).collect::<Vec<u64>>();
+    assert!(res.iter().all(|val| *val < 1_000));
+    assert!(processed.load(Ordering::Relaxed) < vec.len());
+    let res = vec.parallel_iter().map(|val| val * 2).take_while_any(|_| true).count();
+    assert_eq!(res,1_000_000);
+}
+
+#[test]
+fn narrowed_range_test() {
+    // The range is narrowed in place, so only the values taken are ever pulled. Pulling all of it would not fit in
+    // memory.
+    let res = (0..u64::MAX).into_parallel_iter().take(10).map(|val| val).collect::<Vec<u64>>();
+    assert_eq!(res,(0..10).collect::<Vec<u64>>());
+    let res = (0..u64::MAX).into_parallel_iter().skip(u64::MAX as usize - 3).map(|val| val).collect::<Vec<u64>>();
+    assert_eq!(res,vec![u64::MAX - 3, u64::MAX - 2, u64::MAX - 1]);
+    let res = (0..u64::MAX).into_parallel_iter().step_by(1 << 62).map(|val| val).collect::<Vec<u64>>();
+    assert_eq!(res,vec![0, 1 << 62, 2 << 62, 3 << 62]);
+    assert_eq!((0..u64::MAX).into_parallel_iter().skip(7).step_by(1 << 40).take(1_000).len(),Some(1_000));
+}
+
+#[test]
+fn narrowed_slice_test() {
+    let vec = (0..10_000).collect::<Vec<u32>>();
+    let res = vec.parallel_iter().skip(3).step_by(10).skip(2).step_by(3).take(50).map(|val| *val).collect::<Vec<u32>>();
+    assert_eq!(res,vec.iter().skip(3).step_by(10).skip(2).step_by(3).take(50).copied().collect::<Vec<u32>>());
+    let res = vec.clone().into_parallel_iter().step_by(7).skip(5).take(100).map(|val| val).collect::<Vec<u32>>();
+    assert_eq!(res,vec.iter().step_by(7).skip(5).take(100).copied().collect::<Vec<u32>>());
+    assert_eq!(vec.parallel_iter().step_by(usize::MAX).step_by(usize::MAX).map(|val| *val).collect::<Vec<u32>>(),vec![0]);
+    // Adapters on combined sources select from the values pulled
+    let res = vec.parallel_iter().enumerate().skip(9_990).take(5).map(|(idx, _)| idx).collect::<Vec<usize>>();
+    assert_eq!(res,(9_990..9_995).collect::<Vec<usize>>());
+}
@@ -0,0 +1,40 @@
+use parallel_task::prelude::*;
+use parallel_task::utils::cap_to_env_limit;
+
+#[test]
+fn builder_settings_test() {
+    let pool = ThreadPoolBuilder::new()
+    .num_threads(3)
+    .thread_name("pt-worker-")
+    .stack_size(1024 * 1024)
+    .build();
+    // PARALLEL_TASK_NUM_THREADS may be set where the tests are run, and caps every pool
+    assert_eq!(pool.max_threads(),cap_to_env_limit(3));
+    assert_eq!(pool.stack_size(),Some(1024 * 1024));
+
+    let names = (0..10_000).collect::<Vec<usize>>()
+    .parallel_iter()
+    .map(|_| std::thread::current().name().map(str::to_owned).unwrap_or_default())
+    .pool(&pool)
+    .collect::<Vec<_>>();
+    assert!(names.iter().all(|name| name.starts_with("pt-worker-")));
+    assert!(pool.current_threads() <= 3);
+}
+
+#[test]
+fn builder_ratio_test() {
+    let cpus = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    let pool = ThreadPoolBuilder::new().cpu_to_thread_ratio(1).build();
+    assert!(pool.max_threads() <= cpus);
+    // explicit thread count takes precedence over the ratio
+    let pool = ThreadPoolBuilder::new().cpu_to_thread_ratio(1).num_threads(cpus + 5).build();
+    assert_eq!(pool.max_threads(),cap_to_env_limit(cpus + 5));
+}
+
+#[test]
+fn threads_limit_test() {
+    let pool = ThreadPool::new(8);
+    let res = (0..10_000).collect::<Vec<i32>>().parallel_iter().map(|val| *val).pool(&pool).threads(2).collect::<Vec<_>>();
+    assert_eq!(res.len(),10_000);
+    assert!(pool.current_threads() <= 2);
+}
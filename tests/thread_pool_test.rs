@@ -0,0 +1,62 @@
+use parallel_task::prelude::*;
+
+#[test]
+fn pool_reuse_test() {
+    let pool = ThreadPool::new(4);
+    let vec = (0..10_000).collect::<Vec<i32>>();
+    for _ in 0..200 {
+        let res = vec.parallel_iter().map(|val| val + 1).pool(&pool).collect::<Vec<_>>();
+        assert_eq!(res.len(),10_000);
+    }
+    // Threads are leased from the pool and kept alive across calls
+    assert!(pool.current_threads() <= 4);
+}
+
+#[test]
+fn global_pool_many_calls_test() {
+    let total = (0..500).map(|i| {
+        (0..100).collect::<Vec<usize>>().parallel_iter().map(|val| val + i).collect::<Vec<_>>().len()
+    }).sum::<usize>();
+    assert_eq!(total,500 * 100);
+    assert!(ThreadPool::global().current_threads() <= ThreadPool::global().max_threads());
+}
+
+#[test]
+fn nested_calls_test() {
+    // Nested calls on a small pool must not wait on each other for threads
+    let pool = ThreadPool::new(2);
+    let res = (0..50).collect::<Vec<usize>>()
+    .into_parallel_iter()
+    .map(|i| (0..100).collect::<Vec<usize>>().parallel_iter().map(|val| val * i).pool(&pool).collect::<Vec<_>>().len())
+    .pool(&pool)
+    .collect::<Vec<_>>();
+    assert_eq!(res,vec![100;50]);
+}
+
+#[test]
+fn concurrent_callers_test() {
+    let pool = ThreadPool::new(4);
+    std::thread::scope(|s| {
+        let handles = (0..8).map(|_| {
+            let pool = pool.clone();
+            s.spawn(move || {
+                (0..20).map(|_| {
+                    (0..1_000).collect::<Vec<u64>>().into_parallel_iter().map(|val| val * 2).pool(&pool).collect::<Vec<_>>().len()
+                }).sum::<usize>()
+            })
+        }).collect::<Vec<_>>();
+        handles.into_iter().for_each(|h| assert_eq!(h.join().unwrap(),20_000));
+    });
+}
+
+#[test]
+fn pool_scope_test() {
+    let pool = ThreadPool::new(2);
+    let values = (0..1_000).collect::<Vec<i64>>();
+    let (a,b) = pool.scope(|s| {
+        let h1 = s.spawn(|| values[..500].iter().sum::<i64>()).unwrap();
+        let h2 = s.spawn(|| values[500..].iter().sum::<i64>()).unwrap();
+        (h1.join().unwrap(), h2.join().unwrap())
+    });
+    assert_eq!(a + b,values.iter().sum::<i64>());
+}
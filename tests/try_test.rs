@@ -0,0 +1,59 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use parallel_task::prelude::*;
+
+#[test]
+fn try_collect_ok_test() {
+    let res = (0..100_000).collect::<Vec<i32>>()
+    .parallel_iter()
+    .map(|val| if *val >= 0 { Ok(val * 2) } else { Err("negative") })
+    .try_collect::<Vec<i32>>();
+    assert_eq!(res,Ok((0..100_000).map(|val| val * 2).collect::<Vec<i32>>()));
+}
+
+#[test]
+fn try_collect_err_test() {
+    let res = (0..100_000).collect::<Vec<i32>>()
+    .into_parallel_iter()
+    .map(|val| if val != 70_000 { Ok(val) } else { Err(val) })
+    .try_collect::<Vec<i32>>();
+    assert_eq!(res,Err(70_000));
+}
+
+#[test]
+fn try_map_test() {
+    let ok = (0..10_000).map(|val| val.to_string()).collect::<Vec<String>>()
+    .parallel_iter()
+    .try_map(|val| val.parse::<u32>())
+    .collect::<Vec<_>>();
+    assert_eq!(ok.unwrap(),(0..10_000).collect::<Vec<u32>>());
+
+    let mut values = (0..10_000).map(|val| val.to_string()).collect::<Vec<String>>();
+    values[5_000] = "x".to_owned();
+    let err = values.parallel_iter().try_map(|val| val.parse::<u32>()).collect::<Vec<_>>();
+    assert!(err.is_err());
+}
+
+#[test]
+fn try_for_each_test() {
+    let processed = AtomicUsize::new(0);
+    let res = (0..10_000).collect::<Vec<usize>>()
+    .parallel_iter()
+    .try_for_each(|_| { processed.fetch_add(1, Ordering::Relaxed); Ok::<(),String>(()) });
+    assert!(res.is_ok());
+    assert_eq!(processed.load(Ordering::Relaxed),10_000);
+}
+
+#[test]
+fn try_for_each_stops_early_test() {
+    let processed = AtomicUsize::new(0);
+    let res = (0..10_000).collect::<Vec<usize>>()
+    .parallel_iter()
+    .try_for_each(|val| {
+        processed.fetch_add(1, Ordering::Relaxed);
+        std::thread::sleep(std::time::Duration::from_micros(50));
+        if val % 100 == 0 { Err(*val) } else { Ok(()) }
+    });
+    assert_eq!(res.map_err(|val| val % 100),Err(0));
+    // The error stops the other threads without draining their queues
+    assert!(processed.load(Ordering::Relaxed) < 5_000);
+}
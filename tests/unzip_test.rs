@@ -0,0 +1,43 @@
+use std::collections::{BTreeMap, HashSet};
+use parallel_task::prelude::*;
+
+#[test]
+fn unzip_test() {
+    let vec = (0..50_000).collect::<Vec<u64>>();
+    let (squares, strings):(Vec<u64>,Vec<String>) = vec.parallel_iter().map(|val| (val * val, val.to_string())).unzip();
+    assert_eq!(squares,vec.iter().map(|val| val * val).collect::<Vec<u64>>());
+    assert_eq!(strings,vec.iter().map(|val| val.to_string()).collect::<Vec<String>>());
+}
+
+#[test]
+fn unzip_mixed_collectors_test() {
+    let vec = (0..10_000).collect::<Vec<i32>>();
+    let (set, map):(HashSet<i32>,BTreeMap<i32,i32>) = vec.parallel_iter().map(|val| (val % 10, (*val, val * 2))).unzip();
+    assert_eq!(set,(0..10).collect::<HashSet<i32>>());
+    assert_eq!(map.len(),10_000);
+    assert_eq!(map.get(&4_321),Some(&8_642));
+}
+
+#[test]
+fn partition_results_test() {
+    // Uneven job lengths force the partial pairs of several threads to be merged in order
+    let vec = (0..20_000).map(|val| if val % 9 == 0 { "x".to_owned() } else { val.to_string() }).collect::<Vec<String>>();
+    let (ok, failed):(Vec<Result<u32,_>>,Vec<Result<u32,_>>) = vec.parallel_iter().map(|val| {
+        if val.len() % 2 == 0 { std::thread::sleep(std::time::Duration::from_micros(5)); }
+        val.parse::<u32>()
+    }).partition(|res| res.is_ok());
+    let ok = ok.into_iter().map(|res| res.unwrap()).collect::<Vec<u32>>();
+    assert_eq!(ok,(0..20_000).filter(|val| val % 9 != 0).collect::<Vec<u32>>());
+    assert_eq!(failed.len(),vec.iter().filter(|val| *val == "x").count());
+}
+
+#[test]
+fn partition_source_test() {
+    let vec = (0..1_000).collect::<Vec<i32>>();
+    let (low, high):(Vec<i32>,Vec<i32>) = vec.clone().into_parallel_iter().partition(|val| *val < 250);
+    assert_eq!(low,(0..250).collect::<Vec<i32>>());
+    assert_eq!(high,(250..1_000).collect::<Vec<i32>>());
+    let empty:Vec<i32> = Vec::new();
+    let (a, b):(Vec<&i32>,Vec<&i32>) = empty.parallel_iter().partition(|_| true);
+    assert!(a.is_empty() && b.is_empty());
+}